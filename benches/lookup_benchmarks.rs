@@ -0,0 +1,103 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use miau::{
+    builder::ConfigurationBuilder,
+    configuration::{CompoundKey, Configuration, ConfigurationRead, Snapshot},
+    format,
+    source::InMemorySource,
+};
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+const LAYERS: [usize; 4] = [1, 4, 16, 64];
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Settings {
+    service: Service,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Service {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+}
+
+fn configuration(layers: usize) -> Configuration {
+    let mut builder = ConfigurationBuilder::default();
+    for layer in 0..layers {
+        let json = format!(
+            r#"{{ "service" : {{ "name" : "layer{0}", "port" : {0}, "tags" : [ "a", "b", "c" ] }}, "layer{0}" : {{ "value" : {0} }} }}"#,
+            layer
+        );
+        builder.add(InMemorySource::from_string_slice(&json), format::json());
+    }
+
+    builder.build().unwrap()
+}
+
+fn lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+    let key = CompoundKey::try_from("service:port").unwrap();
+
+    for layers in LAYERS.iter() {
+        let configuration = configuration(*layers);
+        let snapshot = configuration.snapshot().unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::new("configuration", layers),
+            &configuration,
+            |b, configuration| {
+                b.iter(|| {
+                    let port: Option<i32> = configuration.get(black_box("service:port"));
+                    port
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("snapshot", layers),
+            &snapshot,
+            |b, snapshot: &Snapshot| {
+                b.iter(|| {
+                    let port: Option<i32> = snapshot.get(black_box("service:port"));
+                    port
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("snapshot_parsed_key", layers),
+            &snapshot,
+            |b, snapshot| b.iter(|| snapshot.lookup::<i32>(black_box(&key))),
+        );
+    }
+
+    group.finish();
+}
+
+fn conversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("conversion");
+
+    for layers in LAYERS.iter() {
+        let configuration = configuration(*layers);
+        let snapshot = configuration.snapshot().unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::new("configuration", layers),
+            &configuration,
+            |b, configuration| {
+                b.iter(|| configuration.lens().try_convert_into::<Settings>().unwrap())
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("snapshot", layers),
+            &snapshot,
+            |b, snapshot| b.iter(|| snapshot.try_convert_into::<Settings>().unwrap()),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, lookup, conversion);
+criterion_main!(benches);
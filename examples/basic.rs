@@ -74,5 +74,5 @@ fn main() {
     };
 
     assert_eq!(Some(&4), stconfig.array.get(3));
-    assert!(stconfig.map.contains_key(&"word".to_string()));
+    assert!(stconfig.map.contains_key("word"));
 }
@@ -1,17 +1,103 @@
 use crate::{
-    configuration::Configuration,
+    configuration::{Configuration, ConfigurationInfo, ConfigurationTree, KeyMode},
     error::ConfigurationError,
     format::Format,
     provider::{AsyncProvider, Provider, ProviderStruct},
     source::{AsyncSource, Source},
 };
-use std::default::Default;
+use futures::{future, stream, StreamExt};
+use std::{collections::HashMap, default::Default};
+
+/// Decides how builders react to providers failing during build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Build fails with the error of the first failing provider. This is the default.
+    FailFast,
+    /// Failing providers are replaced with empty layers marked as skipped.
+    ///
+    /// Error message becomes the [`skip_reason`](crate::configuration::ConfigurationInfo::skip_reason) of the layer,
+    /// so warnings can be listed by iterating over [`infos`](Configuration::infos) of built configuration.
+    Skip,
+    /// All providers are collected and build fails if any of them failed.
+    ///
+    /// If more than one provider failed, returned error is [`MultipleErrors`](crate::error::ErrorCode::MultipleErrors)
+    /// holding errors of all of them in order of registration.
+    CollectAll,
+}
+
+impl Default for ErrorPolicy {
+    fn default() -> Self {
+        ErrorPolicy::FailFast
+    }
+}
+
+struct Collector {
+    policy: ErrorPolicy,
+    key_mode: KeyMode,
+    result: Configuration,
+    errors: Vec<ConfigurationError>,
+}
+
+impl Collector {
+    fn new(policy: ErrorPolicy, key_mode: KeyMode) -> Self {
+        Collector {
+            policy,
+            key_mode,
+            result: Configuration::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        collected: Result<Configuration, ConfigurationError>,
+        info: ConfigurationInfo,
+    ) -> Result<(), ConfigurationError> {
+        let error = match collected {
+            Ok(configuration) => {
+                self.result.roots.extend(configuration.roots);
+                return Ok(());
+            }
+            Err(error) => error,
+        };
+
+        match self.policy {
+            ErrorPolicy::FailFast => return Err(error),
+            ErrorPolicy::Skip => {
+                let reason = error.to_string();
+                self.result.roots.extend(
+                    Configuration::new_singular(
+                        info.mark_skipped(reason),
+                        ConfigurationTree::Map(HashMap::new()),
+                    )
+                    .roots,
+                );
+            }
+            ErrorPolicy::CollectAll => self.errors.push(
+                error.enrich_with_context(format!("Failed to collect configuration : {}", info)),
+            ),
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Configuration, ConfigurationError> {
+        if self.errors.is_empty() {
+            self.result.with_key_mode(self.key_mode)
+        } else {
+            Err(ConfigurationError::combine(self.errors))
+        }
+    }
+}
 
 /// Synchronous configuration builder.
 ///
 /// Owns all sources passed to it and is capable of creating Configuration object.
 pub struct ConfigurationBuilder<'provider> {
     sources: Vec<Box<dyn Provider + 'provider>>,
+    error_policy: ErrorPolicy,
+    key_mode: KeyMode,
+    source_maps: bool,
 }
 
 impl<'provider> Default for ConfigurationBuilder<'provider> {
@@ -32,6 +118,9 @@ impl<'provider> ConfigurationBuilder<'provider> {
     pub fn new() -> Self {
         ConfigurationBuilder {
             sources: Vec::new(),
+            error_policy: ErrorPolicy::default(),
+            key_mode: KeyMode::default(),
+            source_maps: false,
         }
     }
 
@@ -99,9 +188,71 @@ impl<'provider> ConfigurationBuilder<'provider> {
         async_builder
     }
 
+    /// Sets how build reacts to failing providers.
+    ///
+    /// By default build fails with the first error.
+    ///```rust
+    ///use miau::builder::{ConfigurationBuilder, ErrorPolicy};
+    ///use miau::source::FileSource;
+    ///use miau::format;
+    ///
+    ///let mut builder = ConfigurationBuilder::default();
+    ///builder.add(FileSource::from_path("./a/path/to/file.json"), format::json());
+    ///builder.error_policy(ErrorPolicy::Skip);
+    ///
+    ///let configuration = builder.build().unwrap();
+    ///for info in configuration.infos().filter(|info| info.is_skipped()) {
+    ///     println!("Warning : {}", info);
+    ///}
+    ///```
+    pub fn error_policy(&mut self, policy: ErrorPolicy) -> &mut ConfigurationBuilder<'provider> {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Sets how map keys of built configuration are compared.
+    ///
+    /// By default keys have to be identical. Other modes let sources using different conventions override each other,
+    /// e.g. uppercase environment variables and lowercase file keys. See [`KeyMode`] for details.
+    ///```rust
+    ///use miau::{builder::ConfigurationBuilder, configuration::{ConfigurationRead, KeyMode}, format, source::InMemorySource};
+    ///
+    ///let mut builder = ConfigurationBuilder::default();
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "db" : { "host" : "localhost" } }"#), format::json());
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "DB" : { "HOST" : "db.internal" } }"#), format::json());
+    ///builder.key_mode(KeyMode::IgnoreCase);
+    ///
+    ///let configuration = builder.build().unwrap();
+    ///assert_eq!(Some("db.internal".to_string()), configuration.get("Db:Host"));
+    ///```
+    pub fn key_mode(&mut self, mode: KeyMode) -> &mut ConfigurationBuilder<'provider> {
+        self.key_mode = mode;
+        self
+    }
+
+    /// Sets whether providers retain [`SourceMap`](crate::configuration::SourceMap)s of collected configurations.
+    ///
+    /// Source maps let conversion errors and [`locate`](Configuration::locate) point at source locations of values.
+    /// They hold text of every layer and positions of all its nodes, so they are disabled by default.
+    ///```rust
+    ///use miau::{builder::ConfigurationBuilder, format, source::InMemorySource};
+    ///
+    ///let mut builder = ConfigurationBuilder::default();
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "port" : "http" }"#), format::json());
+    ///builder.source_maps(true);
+    ///
+    ///let configuration = builder.build().unwrap();
+    ///assert_eq!(1, configuration.locate("port").unwrap().unwrap().line());
+    ///```
+    pub fn source_maps(&mut self, enabled: bool) -> &mut ConfigurationBuilder<'provider> {
+        self.source_maps = enabled;
+        self
+    }
+
     /// Builds the builder.
     ///
     /// This is function that actually fetches data from all the sources and deserializes them.
+    /// Failing providers are handled according to [`error_policy`](Self::error_policy).
     ///```rust
     ///use miau::builder::ConfigurationBuilder;
     ///use miau::source::FileSource;
@@ -120,16 +271,18 @@ impl<'provider> ConfigurationBuilder<'provider> {
     ///};
     ///```
     pub fn build(&mut self) -> Result<Configuration, ConfigurationError> {
-        let mut result = Configuration::default();
+        let mut collector = Collector::new(self.error_policy, self.key_mode);
 
         for provider in self.sources.iter_mut() {
-            let roots = provider.collect()?;
-            for configuration in roots.roots {
-                result.roots.push(configuration);
-            }
+            let collected = if self.source_maps {
+                provider.collect_with_source_maps()
+            } else {
+                provider.collect()
+            };
+            collector.push(collected, provider.describe())?;
         }
 
-        Ok(result)
+        collector.finish()
     }
 }
 
@@ -143,8 +296,14 @@ impl<'provider> ConfigurationBuilder<'provider> {
 /// Since it handles both synchronous and asynchronous sources it is possible to create
 /// async builder with only synchronous sources. It is discouraged as in such case execution
 /// is the same as in case of synchronous builder, but requires runtime.
+///
+/// Asynchronous providers are collected concurrently, but results are always layered in order of registration.
 pub struct AsyncConfigurationBuilder<'provider> {
     sources: Vec<SourceType<'provider>>,
+    max_concurrency: Option<usize>,
+    error_policy: ErrorPolicy,
+    key_mode: KeyMode,
+    source_maps: bool,
 }
 
 impl<'provider> Default for AsyncConfigurationBuilder<'provider> {
@@ -158,6 +317,15 @@ enum SourceType<'provider> {
     Asynchronous(Box<dyn AsyncProvider + 'provider>),
 }
 
+impl<'provider> SourceType<'provider> {
+    fn describe(&self) -> ConfigurationInfo {
+        match self {
+            SourceType::Synchronous(provider) => provider.describe(),
+            SourceType::Asynchronous(provider) => provider.describe(),
+        }
+    }
+}
+
 impl<'provider> AsyncConfigurationBuilder<'provider> {
     /// Creates new builder.
     ///
@@ -170,6 +338,10 @@ impl<'provider> AsyncConfigurationBuilder<'provider> {
     pub fn new() -> Self {
         AsyncConfigurationBuilder {
             sources: Vec::new(),
+            max_concurrency: None,
+            error_policy: ErrorPolicy::default(),
+            key_mode: KeyMode::default(),
+            source_maps: false,
         }
     }
 
@@ -186,8 +358,12 @@ impl<'provider> AsyncConfigurationBuilder<'provider> {
             sources: builder
                 .sources
                 .drain(..)
-                .map(|s| SourceType::Synchronous(s))
+                .map(SourceType::Synchronous)
                 .collect(),
+            max_concurrency: None,
+            error_policy: builder.error_policy,
+            key_mode: builder.key_mode,
+            source_maps: builder.source_maps,
         }
     }
 
@@ -246,24 +422,83 @@ impl<'provider> AsyncConfigurationBuilder<'provider> {
         self
     }
 
+    /// Limits number of providers collected concurrently.
+    ///
+    /// By default all providers are collected at once. Limit of `0` is treated as `1`.
+    ///```rust
+    ///use miau::builder::AsyncConfigurationBuilder;
+    ///
+    ///let mut builder = AsyncConfigurationBuilder::new();
+    ///builder.max_concurrency(2);
+    ///```
+    pub fn max_concurrency(&mut self, limit: usize) -> &mut AsyncConfigurationBuilder<'provider> {
+        self.max_concurrency = Some(limit.max(1));
+        self
+    }
+
+    /// Sets how build reacts to failing providers.
+    ///
+    /// Similar to [`error_policy`](ConfigurationBuilder::error_policy()) on synchronous builder.
+    pub fn error_policy(
+        &mut self,
+        policy: ErrorPolicy,
+    ) -> &mut AsyncConfigurationBuilder<'provider> {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Sets how map keys of built configuration are compared.
+    ///
+    /// Similar to [`key_mode`](ConfigurationBuilder::key_mode()) on synchronous builder.
+    pub fn key_mode(&mut self, mode: KeyMode) -> &mut AsyncConfigurationBuilder<'provider> {
+        self.key_mode = mode;
+        self
+    }
+
+    /// Sets whether providers retain [`SourceMap`](crate::configuration::SourceMap)s of collected configurations.
+    ///
+    /// Similar to [`source_maps`](ConfigurationBuilder::source_maps()) on synchronous builder.
+    pub fn source_maps(&mut self, enabled: bool) -> &mut AsyncConfigurationBuilder<'provider> {
+        self.source_maps = enabled;
+        self
+    }
+
     /// Builds the builder.
     ///
     /// This is function that actually fetches data from all the sources and deserializes them.
+    /// Providers are collected concurrently, respecting [`max_concurrency`](Self::max_concurrency) limit.
+    /// Failing providers are handled according to [`error_policy`](Self::error_policy),
+    /// with errors considered in order of registration.
     ///
     /// Since it is asynchronous, it requires runtime to be present.
     pub async fn build(&mut self) -> Result<Configuration, ConfigurationError> {
-        let mut result = Configuration::default();
+        let source_maps = self.source_maps;
+        let collections = self.sources.iter().map(|provider| async move {
+            match provider {
+                SourceType::Synchronous(provider) if source_maps => {
+                    provider.collect_with_source_maps()
+                }
+                SourceType::Synchronous(provider) => provider.collect(),
+                SourceType::Asynchronous(provider) if source_maps => {
+                    provider.collect_with_source_maps().await
+                }
+                SourceType::Asynchronous(provider) => provider.collect().await,
+            }
+        });
 
-        for provider in self.sources.iter_mut() {
-            let configuration = match provider {
-                SourceType::Synchronous(provider) => provider.collect()?,
-                SourceType::Asynchronous(provider) => provider.collect().await?,
+        // both preserve order of registration
+        let configurations: Vec<Result<Configuration, ConfigurationError>> =
+            match self.max_concurrency {
+                Some(limit) => stream::iter(collections).buffered(limit).collect().await,
+                None => future::join_all(collections).await,
             };
-            for root in configuration.roots {
-                result.roots.push(root);
-            }
+
+        let mut collector = Collector::new(self.error_policy, self.key_mode);
+
+        for (provider, configuration) in self.sources.iter().zip(configurations) {
+            collector.push(configuration, provider.describe())?;
         }
 
-        Ok(result)
+        collector.finish()
     }
 }
@@ -1,8 +1,11 @@
 use crate::{
-    configuration::{tree, CompoundKey, ConfigurationTree, Value},
+    configuration::{
+        tree::{self, MergedTree},
+        CompoundKey, ConfigurationTree, Key, NodeType, Strict, Value,
+    },
     error::{ConfigurationError, ErrorCode},
 };
-use std::{convert::TryFrom, iter::DoubleEndedIterator};
+use std::{borrow::Cow, collections::BTreeSet, convert::TryFrom, iter::DoubleEndedIterator};
 
 pub fn get_result_internal<'config, T>(
     nodes: impl DoubleEndedIterator<Item = &'config ConfigurationTree>,
@@ -20,10 +23,106 @@ where
     Ok(None)
 }
 
-pub fn merge_cloned<'config>(
-    nodes: impl Iterator<Item = &'config ConfigurationTree>,
-) -> Result<ConfigurationTree, ConfigurationError> {
-    merge_owned(nodes.cloned())
+pub fn get_result_strict_internal<'config, T>(
+    nodes: impl DoubleEndedIterator<Item = &'config ConfigurationTree>,
+    keys: &CompoundKey,
+) -> Result<Option<T>, ConfigurationError>
+where
+    Strict<T>: TryFrom<&'config Value, Error = ConfigurationError>,
+{
+    // value of wrong type is not shadowed by values from lower priority trees
+    match nodes.rev().find(|node| node.descend_many(keys).is_ok()) {
+        Some(node) => node
+            .get_result_internal::<Strict<T>>(keys)
+            .map(|value| value.map(Strict::into_inner)),
+        None => Ok(None),
+    }
+}
+
+// type of the highest priority node decides how nodes are merged
+pub fn node_type<'config>(
+    nodes: impl DoubleEndedIterator<Item = &'config ConfigurationTree>,
+) -> Option<NodeType> {
+    nodes.last().map(|node| node.own_type())
+}
+
+pub fn child_keys<'config>(
+    nodes: impl DoubleEndedIterator<Item = &'config ConfigurationTree> + Clone,
+) -> Vec<Key> {
+    match node_type(nodes.clone()) {
+        Some(NodeType::Map) => nodes
+            .filter_map(|node| match node {
+                ConfigurationTree::Map(map) => Some(map.keys()),
+                _ => None,
+            })
+            .flatten()
+            .collect::<BTreeSet<&String>>()
+            .into_iter()
+            .map(|key| Key::Map(key.clone()))
+            .collect(),
+        Some(NodeType::Array) => (0..array_length(nodes)).map(Key::Array).collect(),
+        Some(NodeType::Value) | None => Vec::new(),
+    }
+}
+
+// shorter arrays only substitute beginning of longer ones when merged
+pub fn array_length<'config>(
+    nodes: impl DoubleEndedIterator<Item = &'config ConfigurationTree> + Clone,
+) -> usize {
+    match node_type(nodes.clone()) {
+        Some(NodeType::Array) => nodes
+            .filter_map(|node| match node {
+                ConfigurationTree::Array(array) => Some(array.len()),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default(),
+        _ => 0,
+    }
+}
+
+// indices counting from the end are resolved against length of arrays created by merging `nodes`,
+// ones that can not be resolved are left for descending to fail on
+pub fn resolve<'config, 'k>(
+    nodes: impl DoubleEndedIterator<Item = &'config ConfigurationTree> + Clone,
+    keys: &'k CompoundKey,
+) -> Result<Cow<'k, CompoundKey>, ConfigurationError> {
+    if !keys.iter().any(Key::is_relative) {
+        return Ok(Cow::Borrowed(keys));
+    }
+
+    let mut nodes: Vec<&'config ConfigurationTree> = nodes.collect();
+    let mut resolved = Vec::with_capacity(keys.len());
+
+    for key in keys.iter() {
+        let key = match key {
+            Key::Slice(..) => return Err(ErrorCode::MisplacedSlice(key.to_string()).into()),
+            Key::ArrayFromEnd(_) => key
+                .resolve_index(array_length(nodes.iter().copied()))
+                .map_or_else(|| key.clone(), Key::Array),
+            other => other.clone(),
+        };
+
+        nodes = nodes
+            .into_iter()
+            .filter_map(|node| node.descend(&key).ok())
+            .collect();
+        resolved.push(key);
+    }
+
+    Ok(Cow::Owned(resolved.into()))
+}
+
+pub fn merge_borrowed<'config>(
+    mut nodes: impl Iterator<Item = &'config ConfigurationTree>,
+) -> Result<MergedTree<'config>, ConfigurationError> {
+    match nodes.next() {
+        Some(node) => nodes.try_fold(MergedTree::Borrowed(node), tree::merge_borrowed),
+        None => {
+            let error: ConfigurationError = ErrorCode::EmptyConfiguration.into();
+            Err(error.enrich_with_context("Failed to merge configurations"))
+        }
+    }
 }
 
 pub fn merge_owned(
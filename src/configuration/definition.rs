@@ -1,11 +1,13 @@
-use super::{ConfigurationInfo, ConfigurationTree};
-use std::convert::From;
+use super::{tree, ConfigurationInfo, ConfigurationTree, KeyMode, SourceMap};
+use crate::error::ConfigurationError;
+use std::{convert::From, sync::Arc};
 
 /// Holds informations about configuration along with configuration tree root.
 #[derive(Debug, Clone)]
 pub struct ConfigurationDefinition {
     pub(crate) info: ConfigurationInfo,
     pub(crate) root: ConfigurationTree,
+    pub(crate) source_map: Option<Arc<SourceMap>>,
 }
 
 /// Borrowed version of [`ConfigurationDefinition`]
@@ -13,12 +15,51 @@ pub struct ConfigurationDefinition {
 pub struct ConfigurationDefinitionLens<'config> {
     pub(crate) info: &'config ConfigurationInfo,
     pub(crate) node: Option<&'config ConfigurationTree>,
+    pub(crate) source_map: Option<&'config Arc<SourceMap>>,
 }
 
 impl ConfigurationDefinition {
     /// Creates new instance of [`ConfigurationDefinition`]
     pub fn new(info: ConfigurationInfo, root: ConfigurationTree) -> Self {
-        ConfigurationDefinition { info, root }
+        ConfigurationDefinition {
+            info,
+            root,
+            source_map: None,
+        }
+    }
+
+    /// Attaches map of positions of nodes in text configuration was parsed from.
+    ///
+    /// It is used to point at source locations in errors.
+    pub fn with_source_map(mut self, source_map: SourceMap) -> Self {
+        self.source_map = Some(Arc::new(source_map));
+        self
+    }
+
+    /// Returns map of positions of nodes in text configuration was parsed from, if available.
+    pub fn source_map(&self) -> Option<&SourceMap> {
+        self.source_map.as_deref()
+    }
+
+    pub(crate) fn normalized(self, mode: KeyMode) -> Result<Self, ConfigurationError> {
+        if mode == KeyMode::Exact {
+            return Ok(self);
+        }
+
+        let ConfigurationDefinition {
+            info,
+            root,
+            source_map,
+        } = self;
+        let root = tree::normalize(root, mode).map_err(|e| {
+            e.enrich_with_context(format!("Failed to normalize keys of : {}", info))
+        })?;
+
+        Ok(ConfigurationDefinition {
+            info,
+            root,
+            source_map: source_map.map(|source_map| Arc::new(source_map.normalized(mode))),
+        })
     }
 }
 
@@ -32,7 +73,8 @@ impl<'config> ConfigurationDefinitionLens<'config> {
     {
         ConfigurationDefinitionLens {
             info: self.info,
-            node: self.node.and_then(|node| func(node)),
+            node: self.node.and_then(func),
+            source_map: self.source_map,
         }
     }
 }
@@ -42,6 +84,7 @@ impl<'config> From<&'config ConfigurationDefinition> for ConfigurationDefinition
         ConfigurationDefinitionLens {
             info: &root_def.info,
             node: Some(&root_def.root),
+            source_map: root_def.source_map.as_ref(),
         }
     }
 }
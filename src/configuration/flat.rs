@@ -0,0 +1,174 @@
+use super::{CompoundKey, ConfigurationTree, Key, NodeType, Value};
+use crate::error::{ConfigurationError, ErrorCode};
+use std::collections::HashMap;
+
+impl ConfigurationTree {
+    /// Returns all values stored in leafs of this tree together with their keys, in depth-first order.
+    ///
+    /// Entries of maps are visited in order of their keys, elements of arrays in order of their indices.
+    /// Leafs containing no value are skipped.
+    ///
+    ///```rust
+    ///use miau::configuration::{ConfigurationTree, Value};
+    ///
+    ///let tree: ConfigurationTree = serde_json::from_str(r#"{ "servers" : [ { "port" : 80 } ], "name" : "app" }"#).unwrap();
+    ///
+    ///let flat: Vec<(String, &Value)> = tree.flatten().map(|(k, v)| (k.to_string(), v)).collect();
+    ///assert_eq!(
+    ///    vec![
+    ///        ("name".to_string(), &Value::String("app".into())),
+    ///        ("servers:[0]:port".to_string(), &Value::SignedInteger(80)),
+    ///    ],
+    ///    flat
+    ///);
+    ///```
+    pub fn flatten(&self) -> impl Iterator<Item = (CompoundKey, &Value)> {
+        let mut leafs = Vec::new();
+        self.flatten_into(&mut Vec::new(), &mut leafs);
+        leafs.into_iter()
+    }
+
+    fn flatten_into<'a>(&'a self, path: &mut Vec<Key>, leafs: &mut Vec<(CompoundKey, &'a Value)>) {
+        match self {
+            ConfigurationTree::Value(Some(value)) => leafs.push((path.clone().into(), value)),
+            ConfigurationTree::Value(None) => {}
+            _ => {
+                for (key, child) in self.children() {
+                    path.push(key);
+                    child.flatten_into(path, leafs);
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    /// Builds tree from values and their keys, which is an inverse of [`flatten`](Self::flatten).
+    ///
+    /// Map keys create maps and array keys create arrays, with missing elements of arrays left empty.
+    /// Array index can exceed number of elements inserted so far by at most 1024,
+    /// larger indices result in [`IndexOutOfRange`](ErrorCode::IndexOutOfRange) error.
+    /// If the same key occurs more than once, the last value is used.
+    /// Keys requiring different node types in the same place result in [`BadNodeMerge`](ErrorCode::BadNodeMerge) error.
+    ///
+    ///```rust
+    ///use miau::configuration::{ConfigurationRead, ConfigurationTree, Value};
+    ///use std::convert::TryInto;
+    ///
+    ///let tree = ConfigurationTree::from_flat(vec![
+    ///    ("servers:[0]:port".try_into().unwrap(), Value::SignedInteger(80)),
+    ///    ("name".try_into().unwrap(), Value::String("app".into())),
+    ///])
+    ///.unwrap();
+    ///
+    ///assert_eq!(Some(80), tree.get("servers:[0]:port"));
+    ///
+    ///let conflict = ConfigurationTree::from_flat(vec![
+    ///    ("servers:[0]".try_into().unwrap(), Value::SignedInteger(80)),
+    ///    ("servers:name".try_into().unwrap(), Value::String("app".into())),
+    ///]);
+    ///assert!(conflict.is_err());
+    ///```
+    pub fn from_flat<I>(values: I) -> Result<ConfigurationTree, ConfigurationError>
+    where
+        I: IntoIterator<Item = (CompoundKey, Value)>,
+    {
+        let mut root = Slot::Empty;
+
+        for (keys, value) in values {
+            root.insert(&keys, value).map_err(|e| {
+                e.enrich_with_context(format!("Failed to insert value under `{}`", keys))
+            })?;
+        }
+
+        Ok(root.into_tree())
+    }
+}
+
+// limits empty elements created by a single index, so that huge indices cannot exhaust memory
+const MAX_ARRAY_GAP: usize = 1024;
+
+// node under construction, which does not have a type until something is inserted into it
+enum Slot {
+    Empty,
+    Value(Value),
+    Map(HashMap<String, Slot>),
+    Array(Vec<Slot>),
+}
+
+impl Slot {
+    fn insert(&mut self, keys: &[Key], value: Value) -> Result<(), ConfigurationError> {
+        let (key, rest) = match keys.split_first() {
+            Some(split) => split,
+            None => {
+                return match self {
+                    Slot::Empty | Slot::Value(_) => {
+                        *self = Slot::Value(value);
+                        Ok(())
+                    }
+                    other => {
+                        Err(ErrorCode::BadNodeMerge(other.node_type(), NodeType::Value).into())
+                    }
+                }
+            }
+        };
+
+        if key.is_relative() {
+            // there are no elements to resolve relative keys against until the tree is built
+            return Err(ErrorCode::MisplacedSlice(key.to_string()).into());
+        }
+
+        if let Slot::Empty = self {
+            *self = match key {
+                Key::Map(_) => Slot::Map(HashMap::new()),
+                _ => Slot::Array(Vec::new()),
+            };
+        }
+
+        let child = match (self, key) {
+            (Slot::Map(map), Key::Map(k)) => map.entry(k.clone()).or_insert(Slot::Empty),
+            (Slot::Array(array), Key::Array(i)) => {
+                if array.len() <= *i {
+                    match i.checked_add(1) {
+                        Some(length) if length - array.len() <= MAX_ARRAY_GAP => {
+                            array.resize_with(length, || Slot::Empty)
+                        }
+                        _ => return Err(ErrorCode::IndexOutOfRange(*i).into()),
+                    }
+                }
+                &mut array[*i]
+            }
+            (other, key) => {
+                let required = match key {
+                    Key::Map(_) => NodeType::Map,
+                    _ => NodeType::Array,
+                };
+                return Err(ErrorCode::BadNodeMerge(other.node_type(), required).into());
+            }
+        };
+
+        child
+            .insert(rest, value)
+            .map_err(|e| e.enrich_with_key(key.clone()))
+    }
+
+    fn node_type(&self) -> NodeType {
+        match self {
+            Slot::Empty | Slot::Value(_) => NodeType::Value,
+            Slot::Map(_) => NodeType::Map,
+            Slot::Array(_) => NodeType::Array,
+        }
+    }
+
+    fn into_tree(self) -> ConfigurationTree {
+        match self {
+            Slot::Empty => ConfigurationTree::Value(None),
+            Slot::Value(value) => ConfigurationTree::Value(Some(value)),
+            Slot::Map(map) => {
+                ConfigurationTree::Map(map.into_iter().map(|(k, v)| (k, v.into_tree())).collect())
+            }
+            Slot::Array(array) => {
+                ConfigurationTree::Array(array.into_iter().map(Slot::into_tree).collect())
+            }
+        }
+    }
+}
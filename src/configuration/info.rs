@@ -1,4 +1,4 @@
-use std::{convert::Into, default::Default, fmt};
+use std::{convert::Into, default::Default, fmt, time::Duration};
 
 /// Holds information about configuration.
 ///
@@ -8,6 +8,8 @@ use std::{convert::Into, default::Default, fmt};
 pub struct ConfigurationInfo {
     pub(crate) source: String,
     pub(crate) format: String,
+    pub(crate) skipped: Option<String>,
+    pub(crate) stale: Option<Duration>,
 }
 
 impl ConfigurationInfo {
@@ -16,9 +18,45 @@ impl ConfigurationInfo {
         ConfigurationInfo {
             source: source.into(),
             format: format.into(),
+            skipped: None,
+            stale: None,
         }
     }
 
+    /// Marks configuration as skipped for given `reason`.
+    ///
+    /// Skipped configuration was not retrieved from its source, e.g. because optional file did not exist.
+    pub fn mark_skipped<T: Into<String>>(mut self, reason: T) -> Self {
+        self.skipped = Some(reason.into());
+        self
+    }
+
+    /// Returns whether configuration was skipped.
+    pub fn is_skipped(&self) -> bool {
+        self.skipped.is_some()
+    }
+
+    /// Returns reason why configuration was skipped, if it was.
+    pub fn skip_reason(&self) -> Option<&str> {
+        self.skipped.as_deref()
+    }
+
+    /// Marks configuration as stale, i.e. served from cache created `age` ago instead of its source.
+    pub fn mark_stale(mut self, age: Duration) -> Self {
+        self.stale = Some(age);
+        self
+    }
+
+    /// Returns whether configuration is stale.
+    pub fn is_stale(&self) -> bool {
+        self.stale.is_some()
+    }
+
+    /// Returns age of cache stale configuration was served from, if it is stale.
+    pub fn cache_age(&self) -> Option<Duration> {
+        self.stale
+    }
+
     /// Returns information about configuration format.
     ///
     /// Usually it will be data format information like `json`, `yaml`.
@@ -44,6 +82,13 @@ impl Default for ConfigurationInfo {
 
 impl fmt::Display for ConfigurationInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "source : {}, format : {}", self.source, self.format)
+        write!(f, "source : {}, format : {}", self.source, self.format)?;
+        if let Some(ref reason) = self.skipped {
+            write!(f, ", skipped : {}", reason)?;
+        }
+        if let Some(age) = self.stale {
+            write!(f, ", stale : cached {}s ago", age.as_secs())?;
+        }
+        Ok(())
     }
 }
@@ -1,7 +1,8 @@
 use crate::{error::ConfigurationError, parsing};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::convert::TryFrom;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 use std::{convert::From, fmt};
 
 ///Multikey for configuration
@@ -16,14 +17,25 @@ pub struct CompoundKey(Vec<Key>);
 
 ///Configuration key
 ///
-///It comes in two flavours - for arrays and maps.
+///It comes in two main flavours - for arrays and maps.
+///Arrays can also be indexed from their end or sliced, which is resolved against actual length of array.
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
 #[serde(untagged)]
+#[non_exhaustive]
 pub enum Key {
     ///Variant that is used to index into arrays
     Array(usize),
     ///Variant that is used as a key in maps
     Map(String),
+    ///Variant that is used to index into arrays counting from their end, with `1` pointing at the last element
+    #[serde(skip)]
+    ArrayFromEnd(usize),
+    ///Variant that is used to select elements of arrays from the first index up to, but excluding, the second one.
+    ///
+    ///Negative indices count from the end of array and missing second index means the end of array.
+    ///Slices can only be used as the last key of [lenses](super::Lens) and deserialized values.
+    #[serde(skip)]
+    Slice(isize, Option<isize>),
 }
 
 impl CompoundKey {
@@ -40,20 +52,124 @@ impl Key {
     /// Panics if `Key` variant is `Array`
     pub fn unwrap_map(&self) -> String {
         match self {
-            Key::Array(_) => panic!("Expected key to be map key!"),
             Key::Map(s) => s.clone(),
+            _ => panic!("Expected key to be map key!"),
         }
     }
     /// Unwraps underlying usize in map variant
     ///
     /// # Panics
-    /// Panics if `Key` variant is `Map`
+    /// Panics if `Key` variant is not `Array`
     pub fn unwrap_array(&self) -> usize {
         match self {
             Key::Array(i) => *i,
-            Key::Map(_) => panic!("Expected key to be array key!"),
+            _ => panic!("Expected key to be array key!"),
         }
     }
+
+    /// Returns index of element pointed at by this key in array of given length, if it is an index within its bounds.
+    pub(crate) fn resolve_index(&self, length: usize) -> Option<usize> {
+        match self {
+            Key::Array(i) if *i < length => Some(*i),
+            Key::ArrayFromEnd(n) if *n > 0 => length.checked_sub(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this key can only be used after resolving it against length of array.
+    pub(crate) fn is_relative(&self) -> bool {
+        std::matches!(self, Key::ArrayFromEnd(_) | Key::Slice(..))
+    }
+}
+
+///Decides which map keys are considered equal when configurations are merged, read and deserialized.
+///
+///It is set with [`ConfigurationBuilder::key_mode`](crate::builder::ConfigurationBuilder::key_mode).
+///Keys of built configuration are stored in their normalized form and keys used to read it are normalized before lookup,
+///while fields of deserialized structs match keys equal to them in this mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMode {
+    ///Keys are equal only if they are identical. This is the default.
+    Exact,
+    ///Keys differing only in case of letters are equal, e.g. `DB` and `db`.
+    IgnoreCase,
+    ///Keys differing only in case of letters or in using `-` instead of `_` are equal, e.g. `Max-Conn` and `max_conn`.
+    IgnoreCaseAndSeparators,
+}
+
+impl Default for KeyMode {
+    fn default() -> Self {
+        KeyMode::Exact
+    }
+}
+
+impl KeyMode {
+    ///Returns form of `key` shared by all keys equal to it in this mode.
+    ///
+    ///Keys are lowercased and, if separators are ignored, have `-` replaced with `_`.
+    pub fn normalize<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        if self.is_normalized(key) {
+            return Cow::Borrowed(key);
+        }
+
+        match self {
+            KeyMode::Exact => Cow::Borrowed(key),
+            KeyMode::IgnoreCase => Cow::Owned(key.to_lowercase()),
+            KeyMode::IgnoreCaseAndSeparators => Cow::Owned(key.to_lowercase().replace('-', "_")),
+        }
+    }
+
+    fn is_normalized(&self, key: &str) -> bool {
+        let lowercase = |c: char| c.to_lowercase().eq(std::iter::once(c));
+        match self {
+            KeyMode::Exact => true,
+            KeyMode::IgnoreCase => key.chars().all(lowercase),
+            KeyMode::IgnoreCaseAndSeparators => key.chars().all(|c| c != '-' && lowercase(c)),
+        }
+    }
+
+    // keys which are already normalized are not copied
+    pub(crate) fn normalize_compound<'a>(&self, keys: &'a CompoundKey) -> Cow<'a, CompoundKey> {
+        let normalized = keys.iter().all(|key| match key {
+            Key::Map(k) => self.is_normalized(k),
+            _ => true,
+        });
+
+        if normalized {
+            Cow::Borrowed(keys)
+        } else {
+            Cow::Owned(self.normalize_keys(keys).into())
+        }
+    }
+
+    pub(crate) fn normalize_key(&self, key: &Key) -> Key {
+        match key {
+            Key::Map(k) => Key::Map(self.normalize(k).into_owned()),
+            other => other.clone(),
+        }
+    }
+
+    pub(crate) fn normalize_keys(&self, keys: &[Key]) -> Vec<Key> {
+        keys.iter().map(|key| self.normalize_key(key)).collect()
+    }
+}
+
+/// Returns range of elements selected by slice with given bounds from array of given length.
+///
+/// Bounds exceeding array are clamped to it.
+pub(crate) fn slice_range(start: isize, end: Option<isize>, length: usize) -> Range<usize> {
+    let resolve = |bound: isize| {
+        if bound < 0 {
+            // negating isize::MIN overflows, but it lies before start of any array anyway
+            length.saturating_sub(bound.checked_neg().map_or(usize::MAX, |b| b as usize))
+        } else {
+            (bound as usize).min(length)
+        }
+    };
+
+    let start = resolve(start);
+    let end = end.map_or(length, resolve).max(start);
+    start..end
 }
 
 impl Deref for CompoundKey {
@@ -79,6 +195,14 @@ impl TryFrom<&str> for CompoundKey {
     }
 }
 
+impl TryFrom<&CompoundKey> for CompoundKey {
+    type Error = ConfigurationError;
+
+    fn try_from(value: &CompoundKey) -> Result<Self, Self::Error> {
+        Ok(value.clone())
+    }
+}
+
 impl TryFrom<String> for CompoundKey {
     type Error = ConfigurationError;
 
@@ -88,11 +212,48 @@ impl TryFrom<String> for CompoundKey {
     }
 }
 
+/// Displays keys in the format accepted when parsing strings into `CompoundKey`, e.g. `servers:[0]:port`.
+///
+/// Map keys that would not be parsed back verbatim, neither as keys nor as [queries](super::Query), are quoted.
+impl fmt::Display for CompoundKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, key) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ":")?;
+            }
+            match key {
+                Key::Map(k) if needs_quoting(k) => {
+                    write!(f, "\"")?;
+                    for c in k.chars() {
+                        if c == '"' || c == '\\' {
+                            write!(f, "\\")?;
+                        }
+                        write!(f, "{}", c)?;
+                    }
+                    write!(f, "\"")?;
+                }
+                other => write!(f, "{}", other)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn needs_quoting(key: &str) -> bool {
+    key.is_empty()
+        || key.starts_with(char::is_whitespace)
+        || key.ends_with(char::is_whitespace)
+        || key.contains(&[':', '[', ']', '"', '\\', '*'][..])
+}
+
 impl fmt::Display for Key {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Key::Array(i) => write!(f, "[{}]", i),
             Key::Map(k) => write!(f, "{}", k),
+            Key::ArrayFromEnd(n) => write!(f, "[-{}]", n),
+            Key::Slice(start, Some(end)) => write!(f, "[{}..{}]", start, end),
+            Key::Slice(start, None) => write!(f, "[{}..]", start),
         }
     }
 }
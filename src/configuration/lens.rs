@@ -1,10 +1,19 @@
 use super::{
-    common, CompoundKey, Configuration, ConfigurationDefinition, ConfigurationDefinitionLens,
-    ConfigurationRead, ConfigurationTree, Value,
+    common, key, CompoundKey, Configuration, ConfigurationDefinition, ConfigurationDefinitionLens,
+    ConfigurationRead, ConfigurationTree, Key, KeyMode, MergedTree, NodeType, OwnedLens, Query,
+    QuerySegment, Strict, Value,
+};
+use crate::{
+    de,
+    error::{ConfigurationError, ErrorCode, SourceLocation},
 };
-use crate::error::ConfigurationError;
 use serde::de::DeserializeOwned;
-use std::convert::{From, TryFrom, TryInto};
+use std::{
+    collections::HashSet,
+    convert::{From, TryFrom, TryInto},
+    ops::Range,
+    sync::Arc,
+};
 
 /// Provides lensing capabilities to a configuration reader.
 ///
@@ -31,7 +40,12 @@ use std::convert::{From, TryFrom, TryInto};
 ///```
 #[derive(Debug)]
 pub struct Lens<'config> {
-    roots: Vec<ConfigurationDefinitionLens<'config>>,
+    pub(crate) roots: Vec<ConfigurationDefinitionLens<'config>>,
+    // keys lensed into so far, counting from root
+    pub(crate) path: Vec<Key>,
+    // elements of arrays visible through lens created with a slice
+    pub(crate) window: Option<Range<usize>>,
+    pub(crate) key_mode: KeyMode,
 }
 
 impl<'config> Lens<'config> {
@@ -41,6 +55,9 @@ impl<'config> Lens<'config> {
     pub fn new_singular(def: &'config ConfigurationDefinition) -> Self {
         Lens {
             roots: vec![def.into()],
+            path: Vec::new(),
+            window: None,
+            key_mode: KeyMode::Exact,
         }
     }
 
@@ -52,26 +69,456 @@ impl<'config> Lens<'config> {
     pub fn new(config: &'config Configuration) -> Self {
         Lens {
             roots: config.roots.iter().map(|r| r.into()).collect(),
+            path: Vec::new(),
+            window: None,
+            key_mode: config.key_mode,
         }
     }
 
     /// Attempts to lens into given `Lens`
     ///
-    /// Function can only return error if transformation of `keys` failed.
+    /// Function can only return error if transformation of `keys` failed or they contain misplaced slice.
     /// If none of configuration trees contains requested key, empty `Lens` will be returned.
+    ///
+    /// Last of `keys` can be a slice, in which case `Lens` only sees selected elements of arrays, indexed from `0`.
+    ///
+    ///```rust
+    ///use miau::{builder::ConfigurationBuilder, configuration::ConfigurationRead, format, source::InMemorySource};
+    ///
+    ///let mut builder = ConfigurationBuilder::default();
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "ports" : [80, 443, 8080, 8443] }"#), format::json());
+    ///
+    ///let configuration = builder.build().unwrap();
+    ///let lens = configuration.lens().try_lens("ports:[1..-1]").unwrap();
+    ///
+    ///assert_eq!(2, lens.len("").unwrap());
+    ///assert_eq!(Some(8080), lens.get("[-1]"));
+    ///```
     pub fn try_lens<S>(&self, keys: S) -> Result<Self, ConfigurationError>
     where
         S: TryInto<CompoundKey, Error = ConfigurationError>,
     {
         let keys = keys.try_into()?;
+        self.lens_into(&keys)
+    }
+
+    fn lens_into(&self, keys: &CompoundKey) -> Result<Self, ConfigurationError> {
+        let (keys, slice) = match keys.split_last() {
+            Some((Key::Slice(start, end), prefix)) => (prefix, Some((*start, *end))),
+            _ => (&keys[..], None),
+        };
+
+        let lens = match self.resolve(keys)? {
+            Some(keys) => self.descend(&keys),
+            None => self.missing(),
+        };
+
+        Ok(match slice {
+            Some((start, end)) => lens.sliced(start, end),
+            None => lens,
+        })
+    }
+
+    fn child(&self, key: &Key) -> Self {
+        match self.resolve(std::slice::from_ref(key)) {
+            Ok(Some(keys)) => self.descend(&keys),
+            _ => self.missing(),
+        }
+    }
+
+    // translates keys relative to this lens into keys relative to its nodes, `None` if they point outside of its window
+    fn resolve(&self, keys: &[Key]) -> Result<Option<CompoundKey>, ConfigurationError> {
+        let mut keys = self.key_mode.normalize_keys(keys);
+
+        if let (Some(window), Some(first)) = (&self.window, keys.first_mut()) {
+            match first {
+                Key::Slice(..) => {
+                    return Err(ErrorCode::MisplacedSlice(first.to_string()).into());
+                }
+                Key::Map(_) => return Ok(None),
+                _ => match first.resolve_index(window.len()) {
+                    Some(index) => *first = Key::Array(window.start + index),
+                    None => return Ok(None),
+                },
+            }
+        }
+
+        common::resolve(self.nodes(), &keys.into()).map(|keys| Some(keys.into_owned()))
+    }
 
+    // keys have to be resolved first
+    pub(crate) fn descend(&self, keys: &CompoundKey) -> Self {
         let new_roots = self
             .roots
             .iter()
-            .map(|def| def.mutate(|node| node.descend_many(&keys).ok()))
+            .map(|def| def.mutate(|node| node.descend_many(keys).ok()))
             .collect();
 
-        Ok(Lens { roots: new_roots })
+        let mut path = self.path.clone();
+        path.extend(keys.iter().cloned());
+
+        Lens {
+            roots: new_roots,
+            path,
+            window: if keys.is_empty() {
+                self.window.clone()
+            } else {
+                None
+            },
+            key_mode: self.key_mode,
+        }
+    }
+
+    pub(crate) fn missing(&self) -> Self {
+        Lens {
+            roots: self.roots.iter().map(|def| def.mutate(|_| None)).collect(),
+            path: self.path.clone(),
+            window: None,
+            key_mode: self.key_mode,
+        }
+    }
+
+    // arrays are sliced lazily, as slicing them right away would detach lens from configurations
+    fn sliced(self, start: isize, end: Option<isize>) -> Self {
+        if common::node_type(self.nodes()) != Some(NodeType::Array) {
+            return self.missing();
+        }
+
+        let (offset, length) = match &self.window {
+            Some(window) => (window.start, window.len()),
+            None => (0, common::array_length(self.nodes())),
+        };
+        let range = key::slice_range(start, end, length);
+
+        Lens {
+            window: Some(offset + range.start..offset + range.end),
+            ..self
+        }
+    }
+
+    /// Returns keys of children of node stored under `keys` in any of configurations.
+    ///
+    /// Keys of maps are a sorted union of keys from all configurations,
+    /// keys of arrays are indices of an array that would be created by merging configurations.
+    /// Empty vector is returned for values and when `keys` can not be found.
+    /// Function can only return error if transformation of `keys` failed.
+    ///
+    ///```rust
+    ///use miau::{builder::ConfigurationBuilder, configuration::Key, format, source::InMemorySource};
+    ///
+    ///let mut builder = ConfigurationBuilder::default();
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "servers" : { "eu" : {} } }"#), format::json());
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "servers" : { "us" : {} } }"#), format::json());
+    ///
+    ///let configuration = builder.build().unwrap();
+    ///let keys = configuration.lens().keys("servers").unwrap();
+    ///assert_eq!(vec![Key::Map("eu".into()), Key::Map("us".into())], keys);
+    ///```
+    pub fn keys<K>(&self, keys: K) -> Result<Vec<Key>, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = keys.try_into()?;
+        Ok(self.lens_into(&keys)?.child_keys())
+    }
+
+    /// Returns number of children of node stored under `keys` in any of configurations.
+    ///
+    /// See [`keys`](Self::keys).
+    pub fn len<K>(&self, keys: K) -> Result<usize, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.keys(keys).map(|keys| keys.len())
+    }
+
+    /// Returns type of node stored under `keys` in the highest priority configuration containing it.
+    ///
+    /// `None` is returned if none of configurations contains `keys`.
+    /// Function can only return error if transformation of `keys` failed.
+    pub fn node_type<K>(&self, keys: K) -> Result<Option<NodeType>, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = keys.try_into()?;
+        Ok(common::node_type(self.lens_into(&keys)?.nodes()))
+    }
+
+    /// Iterates over lenses into children of this `Lens` together with their keys.
+    ///
+    /// Children are ordered like keys returned by [`keys`](Self::keys).
+    pub fn children(&self) -> impl Iterator<Item = (Key, Lens<'config>)> {
+        let children: Vec<_> = self
+            .child_keys()
+            .into_iter()
+            .map(|key| {
+                let lens = self.child(&key);
+                (key, lens)
+            })
+            .collect();
+
+        children.into_iter()
+    }
+
+    /// Iterates over lenses into entries of this `Lens`, sorted by their keys, if it points at maps.
+    pub fn entries(&self) -> impl Iterator<Item = (String, Lens<'config>)> {
+        self.children().filter_map(|(key, lens)| match key {
+            Key::Map(key) => Some((key, lens)),
+            _ => None,
+        })
+    }
+
+    /// Iterates over lenses into elements of this `Lens` if it points at arrays.
+    pub fn elements(&self) -> impl Iterator<Item = Lens<'config>> {
+        self.children().filter_map(|(key, lens)| match key {
+            Key::Array(_) => Some(lens),
+            _ => None,
+        })
+    }
+
+    /// Returns keys of all nodes matching `query`, together with lenses into them, across all configurations.
+    ///
+    /// Matches are ordered depth-first, with children visited like in [`children`](Self::children).
+    /// Function can only return error if transformation of `query` failed.
+    ///
+    ///```rust
+    ///use miau::{builder::ConfigurationBuilder, configuration::ConfigurationRead, format, source::InMemorySource};
+    ///
+    ///let mut builder = ConfigurationBuilder::default();
+    ///builder.add(
+    ///    InMemorySource::from_string_slice(r#"{ "services" : { "web" : { "port" : 80 }, "db" : { "port" : 5432 } } }"#),
+    ///    format::json(),
+    ///);
+    ///
+    ///let configuration = builder.build().unwrap();
+    ///let ports: Vec<(String, Option<i32>)> = configuration
+    ///    .lens()
+    ///    .query("services:*:port")
+    ///    .unwrap()
+    ///    .into_iter()
+    ///    .map(|(key, lens)| (key.to_string(), lens.get("")))
+    ///    .collect();
+    ///
+    ///assert_eq!(
+    ///    vec![("services:db:port".to_string(), Some(5432)), ("services:web:port".to_string(), Some(80))],
+    ///    ports
+    ///);
+    ///```
+    pub fn query<Q>(
+        &self,
+        query: Q,
+    ) -> Result<Vec<(CompoundKey, Lens<'config>)>, ConfigurationError>
+    where
+        Q: TryInto<Query, Error = ConfigurationError>,
+    {
+        let query = query.try_into()?;
+
+        let mut matches = Vec::new();
+        let mut found = HashSet::new();
+        self.query_into(query.segments(), &mut Vec::new(), &mut found, &mut matches);
+
+        Ok(matches)
+    }
+
+    /// Returns keys of all values matching `query` converted into `T`.
+    ///
+    /// Matched maps and arrays, as well as values that can not be converted, are omitted.
+    /// See [`query`](Self::query) for details.
+    pub fn query_values<T, Q>(&self, query: Q) -> Result<Vec<(CompoundKey, T)>, ConfigurationError>
+    where
+        T: TryFrom<&'config Value, Error = ConfigurationError>,
+        Q: TryInto<Query, Error = ConfigurationError>,
+    {
+        let no_keys = CompoundKey::new(Vec::new());
+
+        Ok(self
+            .query(query)?
+            .into_iter()
+            .filter_map(|(key, lens)| {
+                let value = common::get_result_internal(lens.nodes(), &no_keys).ok()??;
+                Some((key, value))
+            })
+            .collect())
+    }
+
+    fn query_into(
+        &self,
+        segments: &[QuerySegment],
+        path: &mut Vec<Key>,
+        found: &mut HashSet<Vec<Key>>,
+        matches: &mut Vec<(CompoundKey, Lens<'config>)>,
+    ) {
+        if self.nodes().next().is_none() {
+            return;
+        }
+
+        let (segment, rest) = match segments.split_first() {
+            Some(split) => split,
+            None => {
+                // `**` can match the same node in many ways
+                if found.insert(path.clone()) {
+                    matches.push((
+                        path.clone().into(),
+                        self.descend(&CompoundKey::new(Vec::new())),
+                    ));
+                }
+                return;
+            }
+        };
+
+        let (keys, rest) = match segment {
+            QuerySegment::Key(key) if key.is_relative() => (self.relative_indices(key), rest),
+            QuerySegment::Key(key) => (vec![self.key_mode.normalize_key(key)], rest),
+            QuerySegment::AnyKey => (
+                self.child_keys()
+                    .into_iter()
+                    .filter(|key| std::matches!(key, Key::Map(_)))
+                    .collect(),
+                rest,
+            ),
+            QuerySegment::AnyIndex => (
+                self.child_keys()
+                    .into_iter()
+                    .filter(|key| std::matches!(key, Key::Array(_)))
+                    .collect(),
+                rest,
+            ),
+            QuerySegment::AnyDescendants => {
+                self.query_into(rest, path, found, matches);
+                (self.child_keys(), segments)
+            }
+        };
+
+        for key in keys {
+            let child = self.child(&key);
+            path.push(key);
+            child.query_into(rest, path, found, matches);
+            path.pop();
+        }
+    }
+
+    pub(crate) fn nodes(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = &'config ConfigurationTree> + Clone + '_ {
+        self.roots.iter().filter_map(|def| def.node)
+    }
+
+    fn child_keys(&self) -> Vec<Key> {
+        match &self.window {
+            Some(window) => (0..window.len()).map(Key::Array).collect(),
+            None => common::child_keys(self.nodes()),
+        }
+    }
+
+    // indices of elements selected by relative key in arrays visible through this lens
+    fn relative_indices(&self, key: &Key) -> Vec<Key> {
+        if common::node_type(self.nodes()) != Some(NodeType::Array) {
+            return Vec::new();
+        }
+
+        let length = self.child_keys().len();
+        match key {
+            Key::Slice(start, end) => key::slice_range(*start, *end, length)
+                .map(Key::Array)
+                .collect(),
+            _ => key
+                .resolve_index(length)
+                .map(Key::Array)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    // subtrees present in a single configuration are borrowed rather than cloned
+    fn merged(&self) -> Result<MergedTree<'config>, ConfigurationError> {
+        let root = common::merge_borrowed(self.nodes())?;
+
+        Ok(match &self.window {
+            Some(window) => root.windowed(window.clone()),
+            None => root,
+        })
+    }
+
+    /// Deserializes value stored under given `keys` into strongly typed struct.
+    ///
+    /// Subtrees stored under `keys` in all configurations are merged, just like in [`try_convert_into`](Self::try_convert_into),
+    /// hence it is possible to read vectors, maps or nested structs without lensing first.
+    /// `None` is returned if none of configurations contains `keys`. Paths of errors count from the root of `Lens`.
+    ///
+    ///```rust
+    ///use miau::{builder::ConfigurationBuilder, format, source::InMemorySource};
+    ///use std::collections::HashMap;
+    ///
+    ///let mut builder = ConfigurationBuilder::default();
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "limits" : { "cpu" : 2, "memory" : 512 } }"#), format::json());
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "limits" : { "cpu" : 4 } }"#), format::json());
+    ///
+    ///let configuration = builder.build().unwrap();
+    ///let limits: HashMap<String, u32> = configuration.lens().get_deserialized("limits").unwrap().unwrap();
+    ///assert_eq!(4, limits["cpu"]);
+    ///assert_eq!(512, limits["memory"]);
+    ///```
+    pub fn get_deserialized<T, K>(&self, keys: K) -> Result<Option<T>, ConfigurationError>
+    where
+        T: DeserializeOwned,
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = keys.try_into()?;
+        let lens = self.lens_into(&keys)?;
+
+        if lens.nodes().next().is_none() {
+            return Ok(None);
+        }
+
+        lens.try_convert_into()
+            .map(Some)
+            .map_err(|e| e.enrich_with_keys(&keys))
+    }
+
+    /// Retrieves value stored under given `keys` using strict conversion.
+    ///
+    /// See [`get_result_strict`](Self::get_result_strict).
+    pub fn get_strict<T, K>(&self, keys: K) -> Option<T>
+    where
+        Strict<T>: TryFrom<&'config Value, Error = ConfigurationError>,
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.get_result_strict(keys).ok().flatten()
+    }
+
+    /// Retrieves value stored under given `keys` using strict conversion.
+    ///
+    /// Only lossless conversions between values of the same type succeed, see [`Strict`] for details.
+    /// Unlike [`get_result`](ConfigurationRead::get_result), value of different type is not skipped in favour of
+    /// lower priority configurations - it results in [`WrongValueType`](crate::error::ErrorCode::WrongValueType) error
+    /// naming configuration which defined it.
+    ///
+    ///```rust
+    ///use miau::{builder::ConfigurationBuilder, configuration::ConfigurationRead, format, source::InMemorySource};
+    ///
+    ///let mut builder = ConfigurationBuilder::default();
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "secure" : false }"#), format::json());
+    ///builder.add(InMemorySource::from_string_slice(r#"{ "secure" : "1" }"#), format::json());
+    ///
+    ///let configuration = builder.build().unwrap();
+    ///let lens = configuration.lens();
+    ///
+    ///let lenient: Option<bool> = lens.get("secure");
+    ///assert_eq!(Some(true), lenient);
+    ///assert!(lens.get_result_strict::<bool, _>("secure").is_err());
+    ///```
+    pub fn get_result_strict<T, K>(&self, keys: K) -> Result<Option<T>, ConfigurationError>
+    where
+        Strict<T>: TryFrom<&'config Value, Error = ConfigurationError>,
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = match self.resolve(&keys.try_into()?)? {
+            Some(keys) => keys,
+            None => return Ok(None),
+        };
+
+        common::get_result_strict_internal(self.nodes(), &keys)
+            .map_err(|e| self.attribute_resolved(e))
     }
 
     /// Deserializes `Lens` into strongly typed struct.
@@ -79,24 +526,218 @@ impl<'config> Lens<'config> {
     /// It is only required that struct to be deserialized to implements `Deserialize`
     /// and contains no borrowed fields, for instance `&str`.
     /// Due to memory model of `miau` it is impossible to deserialize into such fields.
+    ///
+    /// Errors name configuration which defined invalid value and, if it is available, point at its source location.
     pub fn try_convert_into<T: DeserializeOwned>(self) -> Result<T, ConfigurationError> {
-        self.merge_cloned().and_then(|node| node.try_convert_into())
+        let root = self.merged()?;
+        de::deserialize(&root, self.key_mode)
+            .map_err(|e| self.attribute(e.enrich_with_context(de::conversion_context::<T>())))
+    }
+
+    /// Deserializes `Lens` into strongly typed struct, reporting all errors at once.
+    ///
+    /// Works like [`ConfigurationTree::try_convert_into_reporting_all`],
+    /// but every error additionally names configuration which defined invalid value.
+    pub fn try_convert_into_reporting_all<T: DeserializeOwned>(
+        self,
+    ) -> Result<T, ConfigurationError> {
+        let root = self.merged()?;
+
+        de::deserialize_reporting_all(&root, self.key_mode).map_err(|errors| {
+            let errors = errors.into_iter().map(|e| self.attribute(e)).collect();
+            ConfigurationError::combine(errors).enrich_with_context(de::conversion_context::<T>())
+        })
+    }
+
+    /// Deserializes `Lens` into strongly typed struct, reporting keys that were not used by it.
+    ///
+    /// Works like [`ConfigurationTree::try_convert_into_reporting_unknown`],
+    /// but every warning additionally names configuration which defined unknown key.
+    pub fn try_convert_into_reporting_unknown<T: DeserializeOwned>(
+        self,
+    ) -> Result<(T, Vec<ConfigurationError>), ConfigurationError> {
+        let root = self.merged()?;
+
+        let (value, unknown) = de::deserialize_reporting_unknown(&root, self.key_mode)
+            .map_err(|e| self.attribute(e.enrich_with_context(de::conversion_context::<T>())))?;
+
+        Ok((
+            value,
+            unknown.into_iter().map(|e| self.attribute(e)).collect(),
+        ))
+    }
+
+    /// Deserializes `Lens` into strongly typed struct, failing if any key was not used by it.
+    ///
+    /// See [`ConfigurationTree::try_convert_into_denying_unknown`].
+    pub fn try_convert_into_denying_unknown<T: DeserializeOwned>(
+        self,
+    ) -> Result<T, ConfigurationError> {
+        let (value, unknown) = self.try_convert_into_reporting_unknown()?;
+        de::deny_unknown::<T>(value, unknown)
+    }
+
+    /// Returns location in source of value stored under `keys` in the highest priority configuration containing it.
+    ///
+    /// It is only available for configurations that retained their [`SourceMap`](super::SourceMap),
+    /// see [`ConfigurationBuilder::source_maps`](crate::builder::ConfigurationBuilder::source_maps).
+    /// Function can only return error if transformation of `keys` failed.
+    ///
+    ///```rust
+    ///use miau::{builder::ConfigurationBuilder, format, source::InMemorySource};
+    ///
+    ///let mut builder = ConfigurationBuilder::default();
+    ///builder.add(
+    ///    InMemorySource::from_string_slice("{\n  \"port\" : 80\n}"),
+    ///    format::json(),
+    ///);
+    ///builder.source_maps(true);
+    ///
+    ///let configuration = builder.build().unwrap();
+    ///let location = configuration.lens().locate("port").unwrap().unwrap();
+    ///assert_eq!((2, 3), (location.line(), location.column()));
+    ///```
+    pub fn locate<K>(&self, keys: K) -> Result<Option<SourceLocation>, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = match self.resolve(&keys.try_into()?)? {
+            Some(keys) => keys,
+            None => return Ok(None),
+        };
+
+        Ok(self.origin(&keys).and_then(|def| self.location(def, &keys)))
+    }
+
+    // the highest priority configuration containing `keys` is the one that defined their value
+    fn origin(&self, keys: &CompoundKey) -> Option<&ConfigurationDefinitionLens<'config>> {
+        self.roots.iter().rev().find(|def| {
+            def.node
+                .map_or(false, |node| node.descend_many(keys).is_ok())
+        })
+    }
+
+    fn location(
+        &self,
+        def: &ConfigurationDefinitionLens<'config>,
+        keys: &[Key],
+    ) -> Option<SourceLocation> {
+        let source_map = def.source_map?;
+
+        let mut path = self.path.clone();
+        path.extend_from_slice(keys);
+
+        let position = source_map.locate(&path)?;
+        let location = SourceLocation::new(def.info.source(), position.line, position.column);
+
+        Some(match source_map.line(position.line) {
+            Some(line) => location.with_excerpt(line),
+            None => location,
+        })
+    }
+
+    // values that are missing are attributed to their nearest existing ancestor
+    fn attribute(&self, error: ConfigurationError) -> ConfigurationError {
+        let path = error_path(&error);
+        let path = match self.resolve(&path) {
+            Ok(Some(resolved)) => resolved.to_vec(),
+            _ => path,
+        };
+
+        self.attribute_at(error, &path)
+    }
+
+    // for errors which paths are already resolved against nodes
+    fn attribute_resolved(&self, error: ConfigurationError) -> ConfigurationError {
+        let path = error_path(&error);
+        self.attribute_at(error, &path)
+    }
+
+    fn attribute_at(&self, error: ConfigurationError, path: &[Key]) -> ConfigurationError {
+        for length in (1..=path.len()).rev() {
+            let keys = CompoundKey::new(path[..length].to_vec());
+            if let Some(def) = self.origin(&keys) {
+                let error = error.enrich_with_context(format!("Value defined in : {}", def.info));
+                return match self.location(def, &keys) {
+                    Some(location) => error.enrich_with_location(location),
+                    None => error,
+                };
+            }
+        }
+
+        error
     }
 
     /// Merges trees contained in `Lens` into one tree by cloning them.
-    pub fn merge_cloned(mut self) -> Result<ConfigurationTree, ConfigurationError> {
-        common::merge_cloned(self.roots.drain(..).filter_map(|def| def.node))
+    ///
+    /// Only parts of trees present in merged tree are cloned, subtrees overridden by other configurations are skipped.
+    pub fn merge_cloned(self) -> Result<ConfigurationTree, ConfigurationError> {
+        self.merged().map(MergedTree::into_owned)
+    }
+
+    /// Creates [`OwnedLens`] seeing the same values as this `Lens`.
+    ///
+    /// Subtrees this `Lens` points at are cloned, while information about configurations and their source maps are shared.
+    /// To avoid cloning, create `OwnedLens` from [`Arc<Configuration>`](std::sync::Arc) and lens into it instead.
+    pub fn to_owned_lens(&self) -> OwnedLens {
+        let roots = self
+            .roots
+            .iter()
+            .filter_map(|def| {
+                Some(ConfigurationDefinition {
+                    info: def.info.clone(),
+                    root: def.node?.clone(),
+                    source_map: def.source_map.cloned(),
+                })
+            })
+            .collect();
+
+        let configuration = Configuration {
+            roots,
+            key_mode: self.key_mode,
+        };
+
+        OwnedLens::from_parts(
+            Arc::new(configuration),
+            self.path.clone(),
+            CompoundKey::new(Vec::new()),
+            self.window.clone(),
+        )
+    }
+
+    // values borrowed from configurations outlive this `Lens`
+    pub(crate) fn read<T>(&self, keys: &CompoundKey) -> Result<Option<T>, ConfigurationError>
+    where
+        T: TryFrom<&'config Value, Error = ConfigurationError>,
+    {
+        match self.resolve(keys)? {
+            Some(keys) => common::get_result_internal(self.nodes(), &keys),
+            None => Ok(None),
+        }
     }
 }
 
+// paths of errors are stored starting from the innermost key
+fn error_path(error: &ConfigurationError) -> Vec<Key> {
+    error
+        .get_path()
+        .map(|p| p.iter().rev().cloned().collect())
+        .unwrap_or_default()
+}
+
 impl<'config, T, K> ConfigurationRead<'config, T, K> for Lens<'config>
 where
     T: TryFrom<&'config Value, Error = ConfigurationError>,
     K: TryInto<CompoundKey, Error = ConfigurationError>,
 {
     fn get_result(&'config self, keys: K) -> Result<Option<T>, ConfigurationError> {
-        let keys = keys.try_into()?;
-        common::get_result_internal(self.roots.iter().filter_map(|def| def.node), &keys)
+        self.read(&keys.try_into()?)
+    }
+}
+
+impl From<&Lens<'_>> for OwnedLens {
+    fn from(lens: &Lens<'_>) -> Self {
+        lens.to_owned_lens()
     }
 }
 
@@ -0,0 +1,87 @@
+use super::{Key, KeyMode};
+use std::collections::HashMap;
+
+/// Position in text, with lines and columns counted from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// Line number.
+    pub line: usize,
+    /// Column number, counted in characters.
+    pub column: usize,
+}
+
+impl Position {
+    /// Creates new `Position`.
+    pub fn new(line: usize, column: usize) -> Self {
+        Position { line, column }
+    }
+}
+
+/// Maps nodes of configuration tree to positions in text they were parsed from.
+///
+/// Nodes are identified by their paths counting from root.
+/// Map entries are located at their keys, array elements at their first character.
+/// Source maps are created by [`Format::source_map`](crate::format::Format::source_map).
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    positions: HashMap<Vec<Key>, Position>,
+    lines: Vec<String>,
+}
+
+impl SourceMap {
+    /// Creates new empty `SourceMap` of `text`.
+    ///
+    /// Text is retained to provide excerpts of located lines.
+    pub fn new(text: &str) -> Self {
+        SourceMap {
+            positions: HashMap::new(),
+            lines: text.lines().map(|l| l.to_owned()).collect(),
+        }
+    }
+
+    /// Records position of node at `path`, replacing previous one.
+    pub fn insert(&mut self, path: Vec<Key>, position: Position) {
+        self.positions.insert(path, position);
+    }
+
+    /// Returns position of node at `path`, if it was recorded.
+    pub fn get(&self, path: &[Key]) -> Option<Position> {
+        self.positions.get(path).copied()
+    }
+
+    /// Returns position of node at `path` or, if it was not recorded, of its nearest recorded ancestor.
+    pub fn locate(&self, path: &[Key]) -> Option<Position> {
+        (0..=path.len())
+            .rev()
+            .find_map(|length| self.get(&path[..length]))
+    }
+
+    // paths are normalized just like keys of the tree they locate
+    pub(crate) fn normalized(&self, mode: KeyMode) -> SourceMap {
+        SourceMap {
+            positions: self
+                .positions
+                .iter()
+                .map(|(path, position)| (mode.normalize_keys(path), *position))
+                .collect(),
+            lines: self.lines.clone(),
+        }
+    }
+
+    /// Returns content of given line, counting from 1.
+    pub fn line(&self, line: usize) -> Option<&str> {
+        line.checked_sub(1)
+            .and_then(|index| self.lines.get(index))
+            .map(|l| l.as_str())
+    }
+
+    /// Returns number of recorded positions.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns whether no positions were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
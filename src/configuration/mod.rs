@@ -1,20 +1,28 @@
 pub(crate) mod common;
 mod definition;
+mod flat;
 mod info;
 mod key;
 mod lens;
+mod location;
+mod owned;
 mod plural;
+mod query;
 mod read;
+mod snapshot;
 mod tree;
 mod value;
 
 pub use self::definition::{ConfigurationDefinition, ConfigurationDefinitionLens};
 pub use self::info::ConfigurationInfo;
-pub use self::key::{CompoundKey, Key};
+pub use self::key::{CompoundKey, Key, KeyMode};
 pub use self::lens::Lens;
+pub use self::location::{Position, SourceMap};
+pub use self::owned::OwnedLens;
 pub use self::plural::Configuration;
+pub use self::query::{Query, QuerySegment};
 pub use self::read::ConfigurationRead;
+pub use self::snapshot::Snapshot;
+pub(crate) use self::tree::MergedTree;
 pub use self::tree::{ConfigurationTree, NodeType};
-pub use self::value::Value;
-
-pub(crate) use self::tree::merge;
+pub use self::value::{Strict, Value};
@@ -0,0 +1,131 @@
+use super::{CompoundKey, Configuration, ConfigurationRead, Key, Lens, Value};
+use crate::error::ConfigurationError;
+use serde::de::DeserializeOwned;
+use std::{
+    convert::{From, TryFrom, TryInto},
+    ops::Range,
+    sync::Arc,
+};
+
+/// Owned version of [`Lens`].
+///
+/// It shares [`Configuration`] it lenses into, hence it is cheap to clone and can be sent to other threads
+/// or stored in structs without borrowing.
+/// Full functionality of `Lens` is available through [`lens`](Self::lens).
+///
+/// To read values from `OwnedLens` you need to pull [`ConfigurationRead`](super::ConfigurationRead) in scope.
+///
+/// # Example
+///```rust
+///use miau::{builder::ConfigurationBuilder, configuration::{ConfigurationRead, OwnedLens}, format, source::InMemorySource};
+///use std::sync::Arc;
+///
+///let mut builder = ConfigurationBuilder::default();
+///builder.add(InMemorySource::from_string_slice(r#"{ "db" : { "port" : 5432 } }"#), format::json());
+///
+///let configuration = Arc::new(builder.build().unwrap());
+///let db = OwnedLens::new(configuration).try_lens("db").unwrap();
+///
+///let port: Option<i32> = std::thread::spawn(move || db.get("port")).join().unwrap();
+///assert_eq!(Some(5432), port);
+///```
+#[derive(Debug, Clone)]
+pub struct OwnedLens {
+    configuration: Arc<Configuration>,
+    // keys leading to roots of configuration in sources it was parsed from
+    base: Vec<Key>,
+    // keys lensed into so far, counting from roots of configuration
+    keys: CompoundKey,
+    window: Option<Range<usize>>,
+    // set when keys lensed into do not point at any node, reads then return `None` like they do for `Lens`
+    missing: bool,
+}
+
+impl OwnedLens {
+    /// Creates new instance of `OwnedLens` pointing at roots of `configuration`.
+    pub fn new(configuration: Arc<Configuration>) -> Self {
+        OwnedLens::from_parts(
+            configuration,
+            Vec::new(),
+            CompoundKey::new(Vec::new()),
+            None,
+        )
+    }
+
+    pub(crate) fn from_parts(
+        configuration: Arc<Configuration>,
+        base: Vec<Key>,
+        keys: CompoundKey,
+        window: Option<Range<usize>>,
+    ) -> Self {
+        OwnedLens {
+            configuration,
+            base,
+            keys,
+            window,
+            missing: false,
+        }
+    }
+
+    /// Creates [`Lens`] borrowing this `OwnedLens`.
+    pub fn lens(&self) -> Lens<'_> {
+        let mut root = Lens::new(&self.configuration);
+        root.path = self.base.clone();
+
+        let mut lens = root.descend(&self.keys);
+        if self.missing {
+            return lens.missing();
+        }
+
+        lens.window = self.window.clone();
+        lens
+    }
+
+    /// Attempts to lens into given `OwnedLens`.
+    ///
+    /// Works like [`Lens::try_lens`], sharing configuration with this `OwnedLens`.
+    pub fn try_lens<S>(&self, keys: S) -> Result<Self, ConfigurationError>
+    where
+        S: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let lens = self.lens().try_lens(keys)?;
+        let missing = lens.nodes().next().is_none();
+
+        Ok(OwnedLens {
+            configuration: Arc::clone(&self.configuration),
+            base: self.base.clone(),
+            keys: lens.path[self.base.len()..].to_vec().into(),
+            window: lens.window,
+            missing,
+        })
+    }
+
+    /// Deserializes `OwnedLens` into strongly typed struct.
+    ///
+    /// See [`Lens::try_convert_into`].
+    pub fn try_convert_into<T: DeserializeOwned>(&self) -> Result<T, ConfigurationError> {
+        self.lens().try_convert_into()
+    }
+}
+
+impl<'config, T, K> ConfigurationRead<'config, T, K> for OwnedLens
+where
+    T: TryFrom<&'config Value, Error = ConfigurationError>,
+    K: TryInto<CompoundKey, Error = ConfigurationError>,
+{
+    fn get_result(&'config self, keys: K) -> Result<Option<T>, ConfigurationError> {
+        self.lens().read(&keys.try_into()?)
+    }
+}
+
+impl From<Arc<Configuration>> for OwnedLens {
+    fn from(configuration: Arc<Configuration>) -> Self {
+        OwnedLens::new(configuration)
+    }
+}
+
+impl From<Configuration> for OwnedLens {
+    fn from(configuration: Configuration) -> Self {
+        OwnedLens::new(Arc::new(configuration))
+    }
+}
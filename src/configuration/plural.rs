@@ -1,9 +1,9 @@
 use crate::{
     configuration::{
         common, CompoundKey, ConfigurationDefinition, ConfigurationInfo, ConfigurationRead,
-        ConfigurationTree, Lens, Value,
+        ConfigurationTree, Key, KeyMode, Lens, NodeType, Query, Snapshot, Strict, Value,
     },
-    error::ConfigurationError,
+    error::{ConfigurationError, SourceLocation},
 };
 use serde::{de::DeserializeOwned, Deserialize};
 use std::{
@@ -26,10 +26,11 @@ use std::{
 ///let word: Option<String> = configuration.get("word");
 ///assert_eq!(None, word);
 ///```
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(from = "ConfigurationTree")]
 pub struct Configuration {
     pub(crate) roots: Vec<ConfigurationDefinition>,
+    pub(crate) key_mode: KeyMode,
 }
 
 impl Configuration {
@@ -37,12 +38,76 @@ impl Configuration {
     pub fn new_singular(info: ConfigurationInfo, root: ConfigurationTree) -> Self {
         Configuration {
             roots: vec![ConfigurationDefinition::new(info, root)],
+            key_mode: KeyMode::Exact,
         }
     }
 
     /// Creates new empty `Configuration`.
     pub fn new_empty() -> Self {
-        Configuration { roots: vec![] }
+        Configuration {
+            roots: vec![],
+            key_mode: KeyMode::Exact,
+        }
+    }
+
+    /// Adds `root` as configuration of the highest priority, overriding values of all other configurations.
+    ///
+    /// It allows modifying configuration at runtime, e.g. in tests, without rebuilding it.
+    ///
+    ///```rust
+    ///use miau::configuration::{Configuration, ConfigurationInfo, ConfigurationRead, ConfigurationTree};
+    ///
+    ///let mut configuration = Configuration::default();
+    ///
+    ///let mut root = ConfigurationTree::Value(None);
+    ///root.set("port", 8080).unwrap();
+    ///configuration.push_override(ConfigurationInfo::new("tests", "none"), root).unwrap();
+    ///
+    ///assert_eq!(Some(8080), configuration.get("port"));
+    ///```
+    ///
+    /// Fails if keys of `root` become equal after normalization according to [`key_mode`](Self::key_mode),
+    /// while their values cannot be merged.
+    pub fn push_override(
+        &mut self,
+        info: ConfigurationInfo,
+        root: ConfigurationTree,
+    ) -> Result<(), ConfigurationError> {
+        let definition = ConfigurationDefinition::new(info, root).normalized(self.key_mode)?;
+        self.roots.push(definition);
+        Ok(())
+    }
+
+    /// Makes `Configuration` compare map keys according to `mode`.
+    ///
+    /// Keys of all configurations are normalized with [`KeyMode::normalize`], merging entries whose keys became equal,
+    /// and keys used to read `Configuration` are normalized before lookup.
+    /// Normalization can not be undone by switching back to [`KeyMode::Exact`].
+    ///
+    /// Fails with [`BadNodeMerge`](crate::error::ErrorCode::BadNodeMerge) if keys become equal,
+    /// while their values have different types, e.g. `Db` holding a map and `db` a value.
+    ///
+    ///```rust
+    ///use miau::configuration::{Configuration, ConfigurationRead, ConfigurationTree, KeyMode};
+    ///
+    ///let tree: ConfigurationTree = serde_json::from_str(r#"{ "DB" : { "Max-Conn" : 4 } }"#).unwrap();
+    ///let configuration = Configuration::from(tree).with_key_mode(KeyMode::IgnoreCaseAndSeparators).unwrap();
+    ///
+    ///assert_eq!(Some(4), configuration.get("db:max_conn"));
+    ///```
+    pub fn with_key_mode(mut self, mode: KeyMode) -> Result<Self, ConfigurationError> {
+        self.roots = self
+            .roots
+            .into_iter()
+            .map(|def| def.normalized(mode))
+            .collect::<Result<_, _>>()?;
+        self.key_mode = mode;
+        Ok(self)
+    }
+
+    /// Returns mode in which map keys of `Configuration` are compared.
+    pub fn key_mode(&self) -> KeyMode {
+        self.key_mode
     }
 
     /// Creates `Lens` from this `Configuration`.
@@ -50,13 +115,175 @@ impl Configuration {
         self.into()
     }
 
+    /// Creates [`Snapshot`] of this `Configuration`, merging cloned trees of all configurations.
+    ///
+    /// To merge without cloning, convert `Configuration` into `Snapshot` using [`TryFrom`].
+    pub fn snapshot(&self) -> Result<Snapshot, ConfigurationError> {
+        Snapshot::try_from(self)
+    }
+
+    /// Retrieves value stored under given `keys` using strict conversion.
+    ///
+    /// See [`Lens::get_result_strict`].
+    pub fn get_strict<'config, T, K>(&'config self, keys: K) -> Option<T>
+    where
+        Strict<T>: TryFrom<&'config Value, Error = ConfigurationError>,
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.get_result_strict(keys).ok().flatten()
+    }
+
+    /// Retrieves value stored under given `keys` using strict conversion.
+    ///
+    /// Value of different type than requested results in error instead of being skipped.
+    /// See [`Lens::get_result_strict`] for details.
+    pub fn get_result_strict<'config, T, K>(
+        &'config self,
+        keys: K,
+    ) -> Result<Option<T>, ConfigurationError>
+    where
+        Strict<T>: TryFrom<&'config Value, Error = ConfigurationError>,
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.lens().get_result_strict(keys)
+    }
+
+    /// Returns keys of children of node stored under `keys` in any of configurations.
+    ///
+    /// See [`Lens::keys`] for details.
+    pub fn keys<K>(&self, keys: K) -> Result<Vec<Key>, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.lens().keys(keys)
+    }
+
+    /// Returns number of children of node stored under `keys` in any of configurations.
+    ///
+    /// See [`Lens::keys`] for details.
+    pub fn len<K>(&self, keys: K) -> Result<usize, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.lens().len(keys)
+    }
+
+    /// Returns type of node stored under `keys` in the highest priority configuration containing it.
+    ///
+    /// See [`Lens::node_type`] for details.
+    pub fn node_type<K>(&self, keys: K) -> Result<Option<NodeType>, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.lens().node_type(keys)
+    }
+
+    /// Iterates over lenses into children of roots together with their keys.
+    ///
+    /// See [`Lens::children`].
+    pub fn children(&self) -> impl Iterator<Item = (Key, Lens<'_>)> {
+        self.lens().children()
+    }
+
+    /// Iterates over lenses into entries of roots, sorted by their keys.
+    ///
+    /// See [`Lens::entries`].
+    pub fn entries(&self) -> impl Iterator<Item = (String, Lens<'_>)> {
+        self.lens().entries()
+    }
+
+    /// Iterates over lenses into elements of roots if they are arrays.
+    ///
+    /// See [`Lens::elements`].
+    pub fn elements(&self) -> impl Iterator<Item = Lens<'_>> {
+        self.lens().elements()
+    }
+
+    /// Returns keys of all nodes matching `query`, together with lenses into them.
+    ///
+    /// See [`Lens::query`] for details.
+    pub fn query<Q>(&self, query: Q) -> Result<Vec<(CompoundKey, Lens<'_>)>, ConfigurationError>
+    where
+        Q: TryInto<Query, Error = ConfigurationError>,
+    {
+        self.lens().query(query)
+    }
+
+    /// Returns keys of all values matching `query` converted into `T`.
+    ///
+    /// See [`Lens::query_values`] for details.
+    pub fn query_values<'config, T, Q>(
+        &'config self,
+        query: Q,
+    ) -> Result<Vec<(CompoundKey, T)>, ConfigurationError>
+    where
+        T: TryFrom<&'config Value, Error = ConfigurationError>,
+        Q: TryInto<Query, Error = ConfigurationError>,
+    {
+        self.lens().query_values(query)
+    }
+
+    /// Deserializes value stored under given `keys` into strongly typed struct.
+    ///
+    /// See [`Lens::get_deserialized`] for details.
+    pub fn get_deserialized<T, K>(&self, keys: K) -> Result<Option<T>, ConfigurationError>
+    where
+        T: DeserializeOwned,
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.lens().get_deserialized(keys)
+    }
+
     /// Deserializes `Configuration` into strongly typed struct.
     ///
     /// It is only required that struct to be deserialized to implements `Deserialize`
     /// and contains no borrowed fields, for instance `&str`.
     /// Due to memory model of `miau` it is impossible to deserialize into such fields.
+    ///
+    /// Works like [`Lens::try_convert_into`], so configurations are merged without cloning them
+    /// and errors name configuration which defined invalid value.
+    /// If that configuration retained its [`SourceMap`](super::SourceMap), errors point at source location of invalid value.
+    /// Source maps are retained when enabled with [`source_maps`](crate::builder::ConfigurationBuilder::source_maps).
     pub fn try_convert_into<T: DeserializeOwned>(self) -> Result<T, ConfigurationError> {
-        self.merge_owned().and_then(|node| node.try_convert_into())
+        self.lens().try_convert_into()
+    }
+
+    /// Returns location in source of value stored under `keys` in the highest priority configuration containing it.
+    ///
+    /// It is only available for configurations that retained their [`SourceMap`](super::SourceMap).
+    /// See [`Lens::locate`].
+    pub fn locate<K>(&self, keys: K) -> Result<Option<SourceLocation>, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.lens().locate(keys)
+    }
+
+    /// Deserializes `Configuration` into strongly typed struct, reporting all errors at once.
+    ///
+    /// See [`Lens::try_convert_into_reporting_all`] for details.
+    pub fn try_convert_into_reporting_all<T: DeserializeOwned>(
+        self,
+    ) -> Result<T, ConfigurationError> {
+        self.lens().try_convert_into_reporting_all()
+    }
+
+    /// Deserializes `Configuration` into strongly typed struct, reporting keys that were not used by it.
+    ///
+    /// See [`Lens::try_convert_into_reporting_unknown`] for details.
+    pub fn try_convert_into_reporting_unknown<T: DeserializeOwned>(
+        self,
+    ) -> Result<(T, Vec<ConfigurationError>), ConfigurationError> {
+        self.lens().try_convert_into_reporting_unknown()
+    }
+
+    /// Deserializes `Configuration` into strongly typed struct, failing if any key was not used by it.
+    ///
+    /// See [`Lens::try_convert_into_denying_unknown`] for details.
+    pub fn try_convert_into_denying_unknown<T: DeserializeOwned>(
+        self,
+    ) -> Result<T, ConfigurationError> {
+        self.lens().try_convert_into_denying_unknown()
     }
 
     /// Merges trees contained in `Configuration` into one tree by consuming them.
@@ -88,13 +315,18 @@ where
 {
     fn get_result(&'config self, keys: K) -> Result<Option<T>, ConfigurationError> {
         let keys = keys.try_into()?;
+        let keys = self.key_mode.normalize_compound(&keys);
+        let keys = common::resolve(self.roots.iter().map(|def| &def.root), &keys)?;
         common::get_result_internal(self.roots.iter().map(|def| &def.root), &keys)
     }
 }
 
-impl Default for Configuration {
-    fn default() -> Self {
-        Configuration { roots: Vec::new() }
+impl From<ConfigurationDefinition> for Configuration {
+    fn from(definition: ConfigurationDefinition) -> Self {
+        Configuration {
+            roots: vec![definition],
+            key_mode: KeyMode::Exact,
+        }
     }
 }
 
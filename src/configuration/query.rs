@@ -0,0 +1,61 @@
+use super::{CompoundKey, Key};
+use crate::{error::ConfigurationError, parsing};
+use std::convert::TryFrom;
+
+/// Pattern matching keys of configuration nodes.
+///
+/// Queries are usually parsed from strings, using the same format as keys, extended with wildcards:
+/// * `*` matches any key of a map,
+/// * `[*]` matches any index of an array,
+/// * `**` matches any number of keys, including none.
+///
+/// Keys consisting of `*` have to be quoted or escaped to be matched literally, e.g. `"*"`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Query(Vec<QuerySegment>);
+
+/// Single element of [`Query`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum QuerySegment {
+    /// Matches exactly given key
+    Key(Key),
+    /// Matches any key of a map
+    AnyKey,
+    /// Matches any index of an array
+    AnyIndex,
+    /// Matches any sequence of keys, including empty one
+    AnyDescendants,
+}
+
+impl Query {
+    /// Constructs new instance of `Query`
+    pub fn new(segments: Vec<QuerySegment>) -> Self {
+        Query(segments)
+    }
+
+    pub(crate) fn segments(&self) -> &[QuerySegment] {
+        &self.0
+    }
+}
+
+impl From<CompoundKey> for Query {
+    fn from(keys: CompoundKey) -> Self {
+        Query::new(keys.iter().cloned().map(QuerySegment::Key).collect())
+    }
+}
+
+impl TryFrom<&str> for Query {
+    type Error = ConfigurationError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parsing::str_to_query(value)
+            .map_err(|e| e.enrich_with_context(format!("Parsing query `{}` failed", value)))
+    }
+}
+
+impl TryFrom<String> for Query {
+    type Error = ConfigurationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Query::try_from(value.as_str())
+    }
+}
@@ -0,0 +1,148 @@
+use super::{
+    common, CompoundKey, Configuration, ConfigurationDefinition, ConfigurationInfo,
+    ConfigurationRead, ConfigurationTree, Key, KeyMode, Lens, Value,
+};
+use crate::{
+    de,
+    error::{ConfigurationError, ErrorCode},
+};
+use serde::de::DeserializeOwned;
+use std::{
+    convert::{TryFrom, TryInto},
+    iter,
+};
+
+/// Frozen view of [`Configuration`] with all its configurations merged into a single tree.
+///
+/// Merging happens once, when `Snapshot` is created, so lookups no longer visit every configuration
+/// and conversions into structs do not clone and merge trees again.
+/// It does not reflect changes made to `Configuration` after it was created.
+///
+/// Merging drops [`ConfigurationInfo`] of individual configurations, so errors do not name configuration
+/// which defined invalid value. Reads also differ from [`Configuration`], which falls back to lower priority
+/// configurations when value of the highest priority one has wrong type - `Snapshot` only sees the merged value.
+///
+/// Keys can be parsed once and reused with [`lookup`](Self::lookup), which reads them without copying.
+///
+/// To read values from `Snapshot` you need to pull [`ConfigurationRead`](super::ConfigurationRead) in scope.
+/// # Example
+///```rust
+///use miau::{builder::ConfigurationBuilder, configuration::{CompoundKey, ConfigurationRead}, format, source::InMemorySource};
+///use std::convert::TryFrom;
+///
+///let mut builder = ConfigurationBuilder::default();
+///builder.add(InMemorySource::from_string_slice(r#"{ "db" : { "port" : 5432 } }"#), format::json());
+///builder.add(InMemorySource::from_string_slice(r#"{ "db" : { "port" : 6432 } }"#), format::json());
+///
+///let snapshot = builder.build().unwrap().snapshot().unwrap();
+///let port = CompoundKey::try_from("db:port").unwrap();
+///
+///assert_eq!(Some(6432), snapshot.lookup(&port));
+///assert_eq!(Some(6432), snapshot.get("db:port"));
+///```
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    definition: ConfigurationDefinition,
+    key_mode: KeyMode,
+}
+
+impl Snapshot {
+    fn new(root: ConfigurationTree, key_mode: KeyMode) -> Self {
+        Snapshot {
+            definition: ConfigurationDefinition::new(
+                ConfigurationInfo::new("snapshot", "merged"),
+                root,
+            ),
+            key_mode,
+        }
+    }
+
+    /// Retrieves value stored under pre-parsed `keys`.
+    ///
+    /// See [`lookup_result`](Self::lookup_result).
+    pub fn lookup<'config, T>(&'config self, keys: &CompoundKey) -> Option<T>
+    where
+        T: TryFrom<&'config Value, Error = ConfigurationError>,
+    {
+        self.lookup_result(keys).ok().flatten()
+    }
+
+    /// Retrieves value stored under pre-parsed `keys`.
+    ///
+    /// It behaves like [`get_result`](ConfigurationRead::get_result), but `keys` are neither parsed nor copied,
+    /// unless they have to be normalized according to [`KeyMode`] of `Snapshot`.
+    pub fn lookup_result<'config, T>(
+        &'config self,
+        keys: &CompoundKey,
+    ) -> Result<Option<T>, ConfigurationError>
+    where
+        T: TryFrom<&'config Value, Error = ConfigurationError>,
+    {
+        if let Some(slice) = keys.iter().find(|key| std::matches!(key, Key::Slice(..))) {
+            return Err(ErrorCode::MisplacedSlice(slice.to_string()).into());
+        }
+
+        let keys = self.key_mode.normalize_compound(keys);
+        common::get_result_internal(iter::once(&self.definition.root), &keys)
+    }
+
+    /// Deserializes `Snapshot` into strongly typed struct.
+    ///
+    /// Unlike [`Configuration::try_convert_into`], it neither merges nor clones trees.
+    pub fn try_convert_into<T: DeserializeOwned>(&self) -> Result<T, ConfigurationError> {
+        de::deserialize(&self.definition.root, self.key_mode)
+            .map_err(|e| e.enrich_with_context(de::conversion_context::<T>()))
+    }
+
+    /// Creates [`Lens`] from this `Snapshot`.
+    pub fn lens(&self) -> Lens<'_> {
+        let mut lens = Lens::new_singular(&self.definition);
+        lens.key_mode = self.key_mode;
+        lens
+    }
+
+    /// Returns tree created by merging configurations.
+    pub fn root(&self) -> &ConfigurationTree {
+        &self.definition.root
+    }
+}
+
+impl<'config, T, K> ConfigurationRead<'config, T, K> for Snapshot
+where
+    T: TryFrom<&'config Value, Error = ConfigurationError>,
+    K: TryInto<CompoundKey, Error = ConfigurationError>,
+{
+    fn get_result(&'config self, keys: K) -> Result<Option<T>, ConfigurationError> {
+        self.lookup_result(&keys.try_into()?)
+    }
+}
+
+impl TryFrom<&Configuration> for Snapshot {
+    type Error = ConfigurationError;
+
+    fn try_from(configuration: &Configuration) -> Result<Self, Self::Error> {
+        let root = if configuration.roots.is_empty() {
+            ConfigurationTree::Value(None)
+        } else {
+            configuration.lens().merge_cloned()?
+        };
+
+        Ok(Snapshot::new(root, configuration.key_mode))
+    }
+}
+
+impl TryFrom<Configuration> for Snapshot {
+    type Error = ConfigurationError;
+
+    /// Merges trees by moving them out of `configuration`, without cloning.
+    fn try_from(configuration: Configuration) -> Result<Self, Self::Error> {
+        let key_mode = configuration.key_mode;
+        let root = if configuration.roots.is_empty() {
+            ConfigurationTree::Value(None)
+        } else {
+            configuration.merge_owned()?
+        };
+
+        Ok(Snapshot::new(root, key_mode))
+    }
+}
@@ -1,12 +1,16 @@
 use crate::{
-    configuration::{CompoundKey, ConfigurationRead, Key, Value},
+    configuration::{key, CompoundKey, ConfigurationRead, Key, KeyMode, Strict, Value},
+    de,
     error::{ConfigurationError, ErrorCode},
+    ser::TreeSerializer,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
+    borrow::Cow,
     collections::HashMap,
     convert::{TryFrom, TryInto},
     fmt::Display,
+    ops::Range,
 };
 
 /// Stores information from single configuration source.
@@ -36,7 +40,7 @@ pub enum ConfigurationTree {
 }
 
 /// Describes node type of current [`ConfigurationTree`].
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum NodeType {
     /// Node type of [`ConfigurationTree::Value`]
     Value,
@@ -81,21 +85,170 @@ impl ConfigurationTree {
         }
     }
 
+    /// Retrieves value stored under given `keys` using strict conversion.
+    ///
+    /// See [`get_result_strict`](Self::get_result_strict).
+    pub fn get_strict<'a, T, K>(&'a self, keys: K) -> Option<T>
+    where
+        Strict<T>: TryFrom<&'a Value, Error = ConfigurationError>,
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.get_result_strict(keys).ok().flatten()
+    }
+
+    /// Retrieves value stored under given `keys` using strict conversion.
+    ///
+    /// Only lossless conversions between values of the same type succeed, see [`Strict`] for details.
+    /// Value of different type results in [`WrongValueType`](ErrorCode::WrongValueType) error.
+    pub fn get_result_strict<'a, T, K>(&'a self, keys: K) -> Result<Option<T>, ConfigurationError>
+    where
+        Strict<T>: TryFrom<&'a Value, Error = ConfigurationError>,
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = keys.try_into()?;
+        self.get_result_internal::<Strict<T>>(&keys)
+            .map(|value| value.map(Strict::into_inner))
+    }
+
     /// Deserializes `ConfigurationTree` into strongly typed struct.
     ///
     /// It is only required that struct to be deserialized to implements [`Deserialize`](serde::Deserialize)
     /// and contains no borrowed fields, for instance `&str`.
     /// Due to memory model of `miau` it is impossible to deserialize into such fields.
     pub fn try_convert_into<T: DeserializeOwned>(&'_ self) -> Result<T, ConfigurationError> {
-        T::deserialize(self).map_err(|e| {
+        T::deserialize(self).map_err(|e| e.enrich_with_context(de::conversion_context::<T>()))
+    }
+
+    /// Deserializes value stored under given `keys` into strongly typed struct.
+    ///
+    /// `None` is returned if `keys` can not be found. Paths of errors count from the root of this tree.
+    ///
+    ///```rust
+    ///use miau::configuration::ConfigurationTree;
+    ///
+    ///let tree: ConfigurationTree = serde_json::from_str(r#"{ "hosts" : ["a", "b"] }"#).unwrap();
+    ///
+    ///let hosts: Vec<String> = tree.get_deserialized("hosts").unwrap().unwrap();
+    ///assert_eq!(vec!["a", "b"], hosts);
+    ///```
+    pub fn get_deserialized<T, K>(&self, keys: K) -> Result<Option<T>, ConfigurationError>
+    where
+        T: DeserializeOwned,
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = keys.try_into()?;
+
+        match self.find(&keys)? {
+            Some(node) => node
+                .try_convert_into()
+                .map(Some)
+                .map_err(|e| e.enrich_with_keys(&keys)),
+            None => Ok(None),
+        }
+    }
+
+    /// Deserializes `ConfigurationTree` into strongly typed struct, reporting all errors at once.
+    ///
+    /// Unlike [`try_convert_into`](Self::try_convert_into), it does not stop at the first invalid field.
+    /// Every type mismatch and missing field is reported with its path.
+    /// If there is more than one error, [`MultipleErrors`](ErrorCode::MultipleErrors) is returned.
+    ///
+    ///```rust
+    ///use miau::{configuration::ConfigurationTree, error::ErrorCode};
+    ///use serde::Deserialize;
+    ///
+    ///#[derive(Deserialize, Debug)]
+    ///struct Config {
+    ///    port: u16,
+    ///    host: String,
+    ///}
+    ///
+    ///let tree: ConfigurationTree = serde_json::from_str(r#"{ "port" : "http" }"#).unwrap();
+    ///
+    ///let error = tree.try_convert_into_reporting_all::<Config>().unwrap_err();
+    ///match error.get_code() {
+    ///    ErrorCode::MultipleErrors(errors) => assert_eq!(2, errors.len()),
+    ///    _ => panic!("expected multiple errors"),
+    ///}
+    ///```
+    pub fn try_convert_into_reporting_all<T: DeserializeOwned>(
+        &self,
+    ) -> Result<T, ConfigurationError> {
+        de::deserialize_reporting_all(self, KeyMode::Exact).map_err(|errors| {
+            ConfigurationError::combine(errors).enrich_with_context(de::conversion_context::<T>())
+        })
+    }
+
+    /// Deserializes `ConfigurationTree` into strongly typed struct, reporting keys that were not used by it.
+    ///
+    /// Every key for which deserialized type had no field is returned alongside the value
+    /// as an [`UnknownKey`](ErrorCode::UnknownKey) warning with its full path.
+    /// Keys consumed by maps and by fields marked with `#[serde(flatten)]` are never unknown.
+    ///
+    ///```rust
+    ///use miau::{configuration::ConfigurationTree, error::ErrorCode};
+    ///use serde::Deserialize;
+    ///
+    ///#[derive(Deserialize)]
+    ///struct Config {
+    ///    port: u16,
+    ///}
+    ///
+    ///let tree: ConfigurationTree = serde_json::from_str(r#"{ "port" : 80, "prot" : 81 }"#).unwrap();
+    ///
+    ///let (config, warnings) = tree.try_convert_into_reporting_unknown::<Config>().unwrap();
+    ///assert_eq!(80, config.port);
+    ///assert_eq!(1, warnings.len());
+    ///assert!(matches!(warnings[0].get_code(), ErrorCode::UnknownKey(key) if key == "prot"));
+    ///```
+    pub fn try_convert_into_reporting_unknown<T: DeserializeOwned>(
+        &self,
+    ) -> Result<(T, Vec<ConfigurationError>), ConfigurationError> {
+        de::deserialize_reporting_unknown(self, KeyMode::Exact)
+            .map_err(|e| e.enrich_with_context(de::conversion_context::<T>()))
+    }
+
+    /// Deserializes `ConfigurationTree` into strongly typed struct, failing if any key was not used by it.
+    ///
+    /// It is a strict variant of [`try_convert_into_reporting_unknown`](Self::try_convert_into_reporting_unknown).
+    /// If there is more than one unknown key, [`MultipleErrors`](ErrorCode::MultipleErrors) is returned.
+    pub fn try_convert_into_denying_unknown<T: DeserializeOwned>(
+        &self,
+    ) -> Result<T, ConfigurationError> {
+        let (value, unknown) = self.try_convert_into_reporting_unknown()?;
+        de::deny_unknown::<T>(value, unknown)
+    }
+
+    /// Creates `ConfigurationTree` from any value implementing [`Serialize`](serde::Serialize).
+    ///
+    /// It is an inverse of [`try_convert_into`](Self::try_convert_into).
+    /// Structs and maps become map nodes, sequences and tuples become array nodes.
+    /// Keys of serialized maps have to be strings, numbers, bools or chars.
+    ///
+    ///```rust
+    ///use miau::configuration::{ConfigurationRead, ConfigurationTree};
+    ///use serde::Serialize;
+    ///
+    ///#[derive(Serialize)]
+    ///struct Config {
+    ///    port: u16,
+    ///}
+    ///
+    ///let tree = ConfigurationTree::from_serialize(&Config { port: 8080 }).unwrap();
+    ///assert_eq!(Some(8080), tree.get("port"));
+    ///```
+    pub fn from_serialize<T: Serialize + ?Sized>(
+        value: &T,
+    ) -> Result<ConfigurationTree, ConfigurationError> {
+        value.serialize(TreeSerializer).map_err(|e| {
             e.enrich_with_context(format!(
-                "Failed to deserialize configuration to type {}",
+                "Failed to serialize type {} to configuration",
                 std::any::type_name::<T>()
             ))
         })
     }
 
-    pub(crate) fn node_type(&self) -> NodeType {
+    pub(crate) fn own_type(&self) -> NodeType {
         match self {
             ConfigurationTree::Value(_) => NodeType::Value,
             ConfigurationTree::Map(_) => NodeType::Map,
@@ -103,6 +256,119 @@ impl ConfigurationTree {
         }
     }
 
+    /// Returns keys of children of node stored under `keys`.
+    ///
+    /// Keys of maps are sorted, keys of arrays are subsequent indices.
+    /// Empty vector is returned for values and when `keys` can not be found.
+    /// Function can only return error if transformation of `keys` failed.
+    ///
+    ///```rust
+    ///use miau::configuration::{ConfigurationTree, Key};
+    ///
+    ///let tree: ConfigurationTree = serde_json::from_str(r#"{ "servers" : { "b" : 1, "a" : 2 } }"#).unwrap();
+    ///
+    ///let keys = tree.keys("servers").unwrap();
+    ///assert_eq!(vec![Key::Map("a".into()), Key::Map("b".into())], keys);
+    ///```
+    pub fn keys<K>(&self, keys: K) -> Result<Vec<Key>, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = keys.try_into()?;
+        Ok(self
+            .find(&keys)?
+            .map(|node| node.child_keys())
+            .unwrap_or_default())
+    }
+
+    /// Returns number of children of node stored under `keys`.
+    ///
+    /// Values and nodes that can not be found have no children.
+    /// Function can only return error if transformation of `keys` failed.
+    pub fn len<K>(&self, keys: K) -> Result<usize, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        self.keys(keys).map(|keys| keys.len())
+    }
+
+    /// Returns type of node stored under `keys` or `None` if it can not be found.
+    ///
+    /// Function can only return error if transformation of `keys` failed.
+    pub fn node_type<K>(&self, keys: K) -> Result<Option<NodeType>, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = keys.try_into()?;
+        Ok(self.find(&keys)?.map(|node| node.own_type()))
+    }
+
+    // slice ending `keys` selects elements into a new array, other keys that can not be found result in `None`
+    fn find(
+        &self,
+        keys: &CompoundKey,
+    ) -> Result<Option<Cow<'_, ConfigurationTree>>, ConfigurationError> {
+        let (keys, slice) = match keys.split_last() {
+            Some((Key::Slice(start, end), prefix)) => (prefix, Some((*start, *end))),
+            _ => (&keys[..], None),
+        };
+
+        let node = match self.descend_iter(keys.iter()) {
+            Ok(node) => node,
+            Err(e) if std::matches!(e.get_code(), ErrorCode::MisplacedSlice(_)) => return Err(e),
+            Err(_) => return Ok(None),
+        };
+
+        Ok(match (node, slice) {
+            (_, None) => Some(Cow::Borrowed(node)),
+            (ConfigurationTree::Array(array), Some((start, end))) => {
+                let range = key::slice_range(start, end, array.len());
+                Some(Cow::Owned(ConfigurationTree::Array(array[range].to_vec())))
+            }
+            _ => None,
+        })
+    }
+
+    /// Iterates over children of this node together with their keys.
+    ///
+    /// Children are ordered like keys returned by [`keys`](Self::keys). Values have no children.
+    pub fn children(&self) -> impl Iterator<Item = (Key, &ConfigurationTree)> {
+        self.child_keys().into_iter().filter_map(move |key| {
+            let child = self.descend(&key).ok()?;
+            Some((key, child))
+        })
+    }
+
+    /// Iterates over entries of this node, sorted by their keys, if it is a map.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &ConfigurationTree)> {
+        let mut entries: Vec<(&str, &ConfigurationTree)> = match self {
+            ConfigurationTree::Map(map) => map.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+            _ => Vec::new(),
+        };
+        entries.sort_by_key(|(key, _)| *key);
+        entries.into_iter()
+    }
+
+    /// Iterates over elements of this node if it is an array.
+    pub fn elements(&self) -> impl Iterator<Item = &ConfigurationTree> {
+        match self {
+            ConfigurationTree::Array(array) => array.iter(),
+            _ => [].iter(),
+        }
+    }
+
+    pub(crate) fn child_keys(&self) -> Vec<Key> {
+        match self {
+            ConfigurationTree::Value(_) => Vec::new(),
+            ConfigurationTree::Map(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                keys.into_iter().map(|k| Key::Map(k.clone())).collect()
+            }
+            ConfigurationTree::Array(array) => (0..array.len()).map(Key::Array).collect(),
+        }
+    }
+
     pub(crate) fn descend_many(
         &self,
         keys: &CompoundKey,
@@ -124,33 +390,214 @@ impl ConfigurationTree {
     }
 
     pub(crate) fn descend(&self, key: &Key) -> Result<&ConfigurationTree, ConfigurationError> {
-        match self {
-            ConfigurationTree::Value(_) => match key {
-                Key::Array(_) => {
-                    Err(ErrorCode::WrongNodeType(NodeType::Array, NodeType::Value).into())
-                }
-                Key::Map(_) => Err(ErrorCode::WrongNodeType(NodeType::Map, NodeType::Value).into()),
-            },
-            ConfigurationTree::Array(array) => match key {
-                Key::Array(index) => match array.get(*index) {
-                    Some(node) => Ok(node),
-                    None => Err(ErrorCode::IndexOutOfRange(*index).into()),
-                },
-                Key::Map(inner_key) => {
-                    Err(ErrorCode::WrongKeyType(NodeType::Array, inner_key.to_owned()).into())
+        match (self, key) {
+            (ConfigurationTree::Array(array), Key::Array(_))
+            | (ConfigurationTree::Array(array), Key::ArrayFromEnd(_)) => key
+                .resolve_index(array.len())
+                .map(|index| &array[index])
+                .ok_or_else(|| out_of_range(key)),
+            (ConfigurationTree::Map(map), Key::Map(k)) => map
+                .get(k)
+                .ok_or_else(|| ErrorCode::KeyNotFound(k.to_owned()).into()),
+            (node, key) => Err(node.key_mismatch(key)),
+        }
+    }
+
+    fn descend_mut(&mut self, key: &Key) -> Result<&mut ConfigurationTree, ConfigurationError> {
+        match (self, key) {
+            (ConfigurationTree::Array(array), Key::Array(_))
+            | (ConfigurationTree::Array(array), Key::ArrayFromEnd(_)) => {
+                match key.resolve_index(array.len()) {
+                    Some(index) => Ok(&mut array[index]),
+                    None => Err(out_of_range(key)),
                 }
-            },
-            ConfigurationTree::Map(map) => match key {
-                Key::Array(i) => Err(ErrorCode::WrongKeyType(NodeType::Map, i.to_string()).into()),
-                Key::Map(k) => match map.get(k) {
-                    Some(node) => Ok(node),
-                    None => Err(ErrorCode::KeyNotFound(k.to_owned()).into()),
+            }
+            (ConfigurationTree::Map(map), Key::Map(k)) => map
+                .get_mut(k)
+                .ok_or_else(|| ErrorCode::KeyNotFound(k.to_owned()).into()),
+            (node, key) => Err(node.key_mismatch(key)),
+        }
+    }
+
+    // error of descending with `key` which does not fit into this node
+    fn key_mismatch(&self, key: &Key) -> ConfigurationError {
+        match (self, key) {
+            (_, Key::Slice(..)) => ErrorCode::MisplacedSlice(key.to_string()).into(),
+            (ConfigurationTree::Value(_), Key::Map(_)) => {
+                ErrorCode::WrongNodeType(NodeType::Map, NodeType::Value).into()
+            }
+            (ConfigurationTree::Value(_), _) => {
+                ErrorCode::WrongNodeType(NodeType::Array, NodeType::Value).into()
+            }
+            (ConfigurationTree::Array(_), key) => {
+                ErrorCode::WrongKeyType(NodeType::Array, key.to_string()).into()
+            }
+            (ConfigurationTree::Map(_), Key::Array(index)) => {
+                ErrorCode::WrongKeyType(NodeType::Map, index.to_string()).into()
+            }
+            (ConfigurationTree::Map(_), key) => {
+                ErrorCode::WrongKeyType(NodeType::Map, key.to_string()).into()
+            }
+        }
+    }
+
+    /// Returns mutable reference to node stored under `keys`, creating it if it does not exist.
+    ///
+    /// Missing map entries are created, as well as array elements directly following the last one.
+    /// Missing ancestors of the node are created as maps or arrays, depending on type of key used to descend into them.
+    /// Created node, as well as empty value in place of which ancestor is created, is an empty value.
+    ///
+    /// Error is returned if transformation of `keys` failed, if any ancestor is a value
+    /// ([`WrongNodeType`](ErrorCode::WrongNodeType)) or a node of type not matching key
+    /// ([`WrongKeyType`](ErrorCode::WrongKeyType)) or if array index is past the end of array
+    /// ([`IndexOutOfRange`](ErrorCode::IndexOutOfRange)).
+    pub fn entry<K>(&mut self, keys: K) -> Result<&mut ConfigurationTree, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = keys.try_into()?;
+        self.entry_iter(keys.iter())
+    }
+
+    fn entry_iter<'a>(
+        &mut self,
+        mut kiter: impl Iterator<Item = &'a Key>,
+    ) -> Result<&mut ConfigurationTree, ConfigurationError> {
+        match kiter.next() {
+            Some(key) => self
+                .entry_child(key)
+                .and_then(|n| n.entry_iter(kiter))
+                .map_err(|e| e.enrich_with_key(key.clone())),
+            None => Ok(self),
+        }
+    }
+
+    fn entry_child(&mut self, key: &Key) -> Result<&mut ConfigurationTree, ConfigurationError> {
+        if let ConfigurationTree::Value(None) = self {
+            *self = match key {
+                Key::Map(_) => ConfigurationTree::Map(HashMap::new()),
+                Key::Array(_) | Key::ArrayFromEnd(_) => ConfigurationTree::Array(Vec::new()),
+                Key::Slice(..) => return Err(self.key_mismatch(key)),
+            };
+        }
+
+        if let (ConfigurationTree::Array(array), Key::Array(index)) = (&mut *self, key) {
+            if *index == array.len() {
+                array.push(ConfigurationTree::Value(None));
+            }
+        }
+
+        match (self, key) {
+            (ConfigurationTree::Map(map), Key::Map(k)) => Ok(map
+                .entry(k.clone())
+                .or_insert(ConfigurationTree::Value(None))),
+            (node, key) => node.descend_mut(key),
+        }
+    }
+
+    /// Stores `value` under `keys`, replacing node that was stored there.
+    ///
+    /// Missing ancestors are created like in [`entry`](Self::entry), which also lists possible errors.
+    ///
+    ///```rust
+    ///use miau::configuration::{ConfigurationRead, ConfigurationTree, Value};
+    ///
+    ///let mut tree = ConfigurationTree::Value(None);
+    ///tree.set("servers:[0]:port", Value::SignedInteger(80)).unwrap();
+    ///tree.set("servers:[1]:port", 81).unwrap();
+    ///
+    ///assert_eq!(Some(81), tree.get("servers:[1]:port"));
+    ///assert!(tree.set("servers:[3]:port", 83).is_err());
+    ///```
+    pub fn set<K, V>(&mut self, keys: K, value: V) -> Result<(), ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+        V: Into<ConfigurationTree>,
+    {
+        *self.entry(keys)? = value.into();
+        Ok(())
+    }
+
+    /// Removes node stored under `keys` and returns it.
+    ///
+    /// Removing array element shifts all elements after it. Removing root leaves empty value in its place.
+    /// `None` is returned if there is no node under `keys`.
+    /// Error is returned if transformation of `keys` failed or if any ancestor does not match type of key.
+    pub fn remove<K>(&mut self, keys: K) -> Result<Option<ConfigurationTree>, ConfigurationError>
+    where
+        K: TryInto<CompoundKey, Error = ConfigurationError>,
+    {
+        let keys = keys.try_into()?;
+
+        if keys.is_empty() {
+            return Ok(Some(std::mem::replace(
+                self,
+                ConfigurationTree::Value(None),
+            )));
+        }
+
+        self.remove_child(&keys)
+    }
+
+    fn remove_child(
+        &mut self,
+        keys: &[Key],
+    ) -> Result<Option<ConfigurationTree>, ConfigurationError> {
+        let (key, rest) = match keys.split_first() {
+            Some(split) => split,
+            None => return Ok(None),
+        };
+
+        let result = match (self, key) {
+            (ConfigurationTree::Value(None), _) => Ok(None),
+            (ConfigurationTree::Map(map), Key::Map(k)) if rest.is_empty() => Ok(map.remove(k)),
+            (ConfigurationTree::Array(array), Key::Array(_))
+            | (ConfigurationTree::Array(array), Key::ArrayFromEnd(_))
+                if rest.is_empty() =>
+            {
+                Ok(key
+                    .resolve_index(array.len())
+                    .map(|index| array.remove(index)))
+            }
+            (node, key) => match node.descend_mut(key) {
+                Ok(child) => child.remove_child(rest),
+                Err(e) => match e.get_code() {
+                    ErrorCode::KeyNotFound(_) | ErrorCode::IndexOutOfRange(_) => Ok(None),
+                    _ => Err(e),
                 },
             },
-        }
+        };
+
+        result.map_err(|e| e.enrich_with_key(key.clone()))
+    }
+}
+
+fn out_of_range(key: &Key) -> ConfigurationError {
+    match key {
+        Key::ArrayFromEnd(n) => ErrorCode::IndexOutOfRange(*n).into(),
+        Key::Array(i) => ErrorCode::IndexOutOfRange(*i).into(),
+        _ => ErrorCode::MisplacedSlice(key.to_string()).into(),
     }
 }
 
+impl From<Value> for ConfigurationTree {
+    fn from(value: Value) -> Self {
+        ConfigurationTree::Value(Some(value))
+    }
+}
+
+macro_rules! from_for_tree {
+    ($($t:ty),*) => {
+        $(impl From<$t> for ConfigurationTree {
+            fn from(value: $t) -> Self {
+                ConfigurationTree::Value(Some(value.into()))
+            }
+        })*
+    };
+}
+
+from_for_tree!(i8, i16, i32, i64, u8, u16, u32, f32, f64, bool, String, &str);
+
 impl<'config, T, K> ConfigurationRead<'config, T, K> for ConfigurationTree
 where
     T: TryFrom<&'config Value, Error = ConfigurationError>,
@@ -162,6 +609,62 @@ where
     }
 }
 
+// keys that become equal are merged in order of their original spelling,
+// nodes of different types cannot be merged as neither of them is more specific
+pub(crate) fn normalize(
+    node: ConfigurationTree,
+    mode: KeyMode,
+) -> Result<ConfigurationTree, ConfigurationError> {
+    if mode == KeyMode::Exact {
+        return Ok(node);
+    }
+
+    match node {
+        ConfigurationTree::Map(map) => {
+            let mut entries: Vec<(String, ConfigurationTree)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            let mut normalized = HashMap::with_capacity(entries.len());
+            for (key, node) in entries {
+                let key = mode.normalize(&key).into_owned();
+                let node = normalize(node, mode).and_then(|node| match normalized.remove(&key) {
+                    Some(previous) => merge_normalized(previous, node),
+                    None => Ok(node),
+                });
+
+                match node {
+                    Ok(node) => normalized.insert(key, node),
+                    Err(e) => return Err(e.enrich_with_key(Key::Map(key))),
+                };
+            }
+
+            Ok(ConfigurationTree::Map(normalized))
+        }
+        ConfigurationTree::Array(array) => array
+            .into_iter()
+            .enumerate()
+            .map(|(index, node)| {
+                normalize(node, mode).map_err(|e| e.enrich_with_key(Key::Array(index)))
+            })
+            .collect::<Result<_, _>>()
+            .map(ConfigurationTree::Array),
+        value => Ok(value),
+    }
+}
+
+fn merge_normalized(
+    previous: ConfigurationTree,
+    next: ConfigurationTree,
+) -> Result<ConfigurationTree, ConfigurationError> {
+    if previous.own_type() == next.own_type() {
+        return merge(previous, next);
+    }
+
+    let error: ConfigurationError =
+        ErrorCode::BadNodeMerge(previous.own_type(), next.own_type()).into();
+    Err(error.enrich_with_context("Failed to merge keys which became equal after normalization"))
+}
+
 pub(crate) fn merge(
     previous: ConfigurationTree,
     next: ConfigurationTree,
@@ -174,45 +677,37 @@ pub(crate) fn merge(
         (ConfigurationTree::Array(vp), ConfigurationTree::Array(vn)) => {
             Ok(ConfigurationTree::Array(merge_arrays(vp, vn)))
         }
-        (vp, vm) => Err(ErrorCode::BadNodeMerge(vp.node_type(), vm.node_type()).into()),
+        (vp, vm) => Err(ErrorCode::BadNodeMerge(vp.own_type(), vm.own_type()).into()),
     }
 }
 
 fn merge_maps(
     mut previous: HashMap<String, ConfigurationTree>,
-    mut next: HashMap<String, ConfigurationTree>,
+    next: HashMap<String, ConfigurationTree>,
 ) -> Result<HashMap<String, ConfigurationTree>, ConfigurationError> {
-    for (key, next_node) in next.drain() {
-        if !previous.contains_key(&key) {
-            previous.insert(key.clone(), next_node.clone());
-        } else {
-            let previous_node = previous.remove(&key).unwrap();
-            match (previous_node, next_node) {
-                (ConfigurationTree::Value(_), vn @ ConfigurationTree::Value(_)) => {
-                    previous.insert(key.clone(), vn.clone());
-                }
-                (ConfigurationTree::Map(mp), ConfigurationTree::Map(mn)) => {
-                    previous.insert(
-                        key.clone(),
-                        ConfigurationTree::Map(
-                            merge_maps(mp, mn)
-                                .map_err(|e| e.enrich_with_key(Key::Map(key.clone())))?,
-                        ),
-                    );
-                }
+    for (key, next_node) in next {
+        let node = match previous.remove(&key) {
+            None => next_node,
+            Some(previous_node) => match (previous_node, next_node) {
+                (ConfigurationTree::Value(_), vn @ ConfigurationTree::Value(_)) => vn,
+                (ConfigurationTree::Map(mp), ConfigurationTree::Map(mn)) => ConfigurationTree::Map(
+                    merge_maps(mp, mn).map_err(|e| e.enrich_with_key(Key::Map(key.clone())))?,
+                ),
                 (ConfigurationTree::Array(vp), ConfigurationTree::Array(vn)) => {
-                    previous.insert(key.clone(), ConfigurationTree::Array(merge_arrays(vp, vn)));
+                    ConfigurationTree::Array(merge_arrays(vp, vn))
                 }
                 (vp, vn) => {
                     let error: ConfigurationError =
-                        ErrorCode::BadNodeMerge(vp.node_type(), vn.node_type()).into();
+                        ErrorCode::BadNodeMerge(vp.own_type(), vn.own_type()).into();
 
                     return Err(error
                         .enrich_with_context("Failed to merge maps")
                         .enrich_with_key(Key::Map(key)));
                 }
-            }
-        }
+            },
+        };
+
+        previous.insert(key, node);
     }
 
     Ok(previous)
@@ -222,17 +717,146 @@ fn merge_arrays(
     mut vp: Vec<ConfigurationTree>,
     vn: Vec<ConfigurationTree>,
 ) -> Vec<ConfigurationTree> {
-    if vp.len() >= vn.len() {
-        for (index, root) in vn.iter().enumerate() {
-            vp[index] = root.clone();
+    if vp.len() < vn.len() {
+        return vn;
+    }
+
+    for (index, root) in vn.into_iter().enumerate() {
+        vp[index] = root;
+    }
+
+    vp
+}
+
+/// Result of merging borrowed trees.
+///
+/// Subtrees taken over from a single tree stay borrowed, only nodes combining multiple trees are created.
+/// Follows the same rules as [`merge`], so that the same trees are merged into equal results.
+#[derive(Debug)]
+pub(crate) enum MergedTree<'config> {
+    Borrowed(&'config ConfigurationTree),
+    Map(HashMap<&'config str, MergedTree<'config>>),
+    Array(Vec<MergedTree<'config>>),
+}
+
+impl<'config> MergedTree<'config> {
+    pub(crate) fn own_type(&self) -> NodeType {
+        match self {
+            MergedTree::Borrowed(node) => node.own_type(),
+            MergedTree::Map(_) => NodeType::Map,
+            MergedTree::Array(_) => NodeType::Array,
         }
-    } else {
-        vp.clear();
-        for e in vn.iter() {
-            vp.push(e.clone())
+    }
+
+    // replaces borrowed map or array with a node borrowing its children
+    fn expand(self) -> Self {
+        match self {
+            MergedTree::Borrowed(ConfigurationTree::Map(map)) => MergedTree::Map(
+                map.iter()
+                    .map(|(key, node)| (key.as_str(), MergedTree::Borrowed(node)))
+                    .collect(),
+            ),
+            MergedTree::Borrowed(ConfigurationTree::Array(array)) => {
+                MergedTree::Array(array.iter().map(MergedTree::Borrowed).collect())
+            }
+            other => other,
         }
     }
 
+    /// Narrows array to elements in `window`, leaving other nodes intact.
+    pub(crate) fn windowed(self, window: Range<usize>) -> Self {
+        match self.expand() {
+            MergedTree::Array(mut array) => {
+                array.truncate(window.end);
+                array.drain(..window.start);
+                MergedTree::Array(array)
+            }
+            other => other,
+        }
+    }
+
+    /// Creates tree, cloning subtrees borrowed from merged trees.
+    pub(crate) fn into_owned(self) -> ConfigurationTree {
+        match self {
+            MergedTree::Borrowed(node) => node.clone(),
+            MergedTree::Map(map) => ConfigurationTree::Map(
+                map.into_iter()
+                    .map(|(key, node)| (key.to_owned(), node.into_owned()))
+                    .collect(),
+            ),
+            MergedTree::Array(array) => {
+                ConfigurationTree::Array(array.into_iter().map(MergedTree::into_owned).collect())
+            }
+        }
+    }
+}
+
+pub(crate) fn merge_borrowed<'config>(
+    previous: MergedTree<'config>,
+    next: &'config ConfigurationTree,
+) -> Result<MergedTree<'config>, ConfigurationError> {
+    if let ConfigurationTree::Value(_) = next {
+        return Ok(MergedTree::Borrowed(next));
+    }
+
+    match (previous.expand(), next) {
+        (MergedTree::Map(mp), ConfigurationTree::Map(mn)) => {
+            Ok(MergedTree::Map(merge_borrowed_maps(mp, mn)?))
+        }
+        (MergedTree::Array(vp), ConfigurationTree::Array(vn)) => {
+            Ok(MergedTree::Array(merge_borrowed_arrays(vp, vn)))
+        }
+        (vp, vn) => Err(ErrorCode::BadNodeMerge(vp.own_type(), vn.own_type()).into()),
+    }
+}
+
+fn merge_borrowed_maps<'config>(
+    mut previous: HashMap<&'config str, MergedTree<'config>>,
+    next: &'config HashMap<String, ConfigurationTree>,
+) -> Result<HashMap<&'config str, MergedTree<'config>>, ConfigurationError> {
+    for (key, next_node) in next {
+        let node = match previous.remove(key.as_str()) {
+            None => MergedTree::Borrowed(next_node),
+            Some(previous_node) => match (previous_node.expand(), next_node) {
+                (MergedTree::Borrowed(_), ConfigurationTree::Value(_)) => {
+                    MergedTree::Borrowed(next_node)
+                }
+                (MergedTree::Map(mp), ConfigurationTree::Map(mn)) => MergedTree::Map(
+                    merge_borrowed_maps(mp, mn)
+                        .map_err(|e| e.enrich_with_key(Key::Map(key.clone())))?,
+                ),
+                (MergedTree::Array(vp), ConfigurationTree::Array(vn)) => {
+                    MergedTree::Array(merge_borrowed_arrays(vp, vn))
+                }
+                (vp, vn) => {
+                    let error: ConfigurationError =
+                        ErrorCode::BadNodeMerge(vp.own_type(), vn.own_type()).into();
+
+                    return Err(error
+                        .enrich_with_context("Failed to merge maps")
+                        .enrich_with_key(Key::Map(key.clone())));
+                }
+            },
+        };
+
+        previous.insert(key.as_str(), node);
+    }
+
+    Ok(previous)
+}
+
+fn merge_borrowed_arrays<'config>(
+    mut vp: Vec<MergedTree<'config>>,
+    vn: &'config [ConfigurationTree],
+) -> Vec<MergedTree<'config>> {
+    if vp.len() < vn.len() {
+        return vn.iter().map(MergedTree::Borrowed).collect();
+    }
+
+    for (index, root) in vn.iter().enumerate() {
+        vp[index] = MergedTree::Borrowed(root);
+    }
+
     vp
 }
 
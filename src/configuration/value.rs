@@ -46,6 +46,22 @@ impl fmt::Display for Value {
     }
 }
 
+macro_rules! from_for_value {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(v: $t) -> Self {
+                    Value::$variant(<$target>::from(v))
+                }
+            }
+        )*
+    };
+}
+from_for_value!(SignedInteger, i64, i8, i16, i32, i64, u8, u16, u32);
+from_for_value!(Float, f64, f32, f64);
+from_for_value!(Bool, bool, bool);
+from_for_value!(String, String, String, &str);
+
 macro_rules! try_from_for_int {
     ($($t:ty),*) => {
         $(
@@ -170,3 +186,120 @@ impl<'conf> TryFrom<&'conf Value> for &'conf str {
         }
     }
 }
+
+/// Wrapper selecting strict conversion of [`Value`] into `T`.
+///
+/// Regular conversions are lenient - `"1"` becomes `true`, `3.9` becomes `3` and `true` becomes `1`.
+/// `Strict` only accepts values of the same type that can be converted without loss:
+/// integers that fit into target type, floats within range of target float, booleans and strings.
+/// Integers are not converted into floats, nor floats into integers. Floats are rounded to the nearest `f32`, just like when parsing them from text. Every other conversion fails with [`WrongValueType`](ErrorCode::WrongValueType).
+///
+/// Strict conversion is selected for a single read by asking for `Strict<T>` instead of `T`.
+///
+///```rust
+///use miau::configuration::{ConfigurationRead, ConfigurationTree, Strict};
+///use std::convert::TryInto;
+///
+///let tree: ConfigurationTree = serde_json::from_str(r#"{ "enabled" : "1", "limit" : 3.9 }"#).unwrap();
+///
+///let enabled: Option<bool> = tree.get("enabled");
+///assert_eq!(Some(true), enabled);
+///
+///let enabled: Result<Option<Strict<bool>>, _> = tree.get_result("enabled");
+///assert!(enabled.is_err());
+///
+///let limit: Result<Option<Strict<i32>>, _> = tree.get_result("limit");
+///assert!(limit.is_err());
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Strict<T>(pub T);
+
+impl<T> Strict<T> {
+    /// Returns wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn strict_mismatch(expected: &str, value: &Value) -> ConfigurationError {
+    ErrorCode::WrongValueType(expected.into(), value.display_type().into()).into()
+}
+
+macro_rules! try_from_strict_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<&Value> for Strict<$t> {
+                type Error = ConfigurationError;
+
+                fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::SignedInteger(v) => (*v).try_into()
+                            .map(Strict)
+                            .map_err(|_| ErrorCode::WrongValueType(stringify!($t).into(), "i64".into()).into()),
+                        other => Err(strict_mismatch(stringify!($t), other)),
+                    }
+                }
+            }
+        )*
+    };
+}
+try_from_strict_for_int!(i8, i16, i32, i64, isize);
+
+macro_rules! try_from_strict_for_float {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<&Value> for Strict<$t> {
+                type Error = ConfigurationError;
+
+                fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::Float(v) => {
+                            // rounding is expected, as most decimal fractions like 0.1 have no exact representation
+                            let converted = *v as $t;
+                            if converted.is_finite() || !v.is_finite() {
+                                Ok(Strict(converted))
+                            } else {
+                                Err(ErrorCode::WrongValueType(stringify!($t).into(), "f64 out of range".into()).into())
+                            }
+                        }
+                        other => Err(strict_mismatch(stringify!($t), other)),
+                    }
+                }
+            }
+        )*
+    };
+}
+try_from_strict_for_float!(f32, f64);
+
+impl TryFrom<&Value> for Strict<bool> {
+    type Error = ConfigurationError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Bool(v) => Ok(Strict(*v)),
+            other => Err(strict_mismatch("bool", other)),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Strict<String> {
+    type Error = ConfigurationError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(v) => Ok(Strict(v.clone())),
+            other => Err(strict_mismatch("string", other)),
+        }
+    }
+}
+
+impl<'conf> TryFrom<&'conf Value> for Strict<&'conf str> {
+    type Error = ConfigurationError;
+
+    fn try_from(value: &'conf Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(v) => Ok(Strict(v.as_str())),
+            other => Err(strict_mismatch("string", other)),
+        }
+    }
+}
@@ -1,161 +1,611 @@
 use crate::{
-    configuration::{ConfigurationTree, Key, Value},
-    error::ConfigurationError,
+    configuration::{ConfigurationTree, Key, KeyMode, MergedTree, NodeType, Value},
+    error::{ConfigurationError, ErrorCode},
 };
 use serde::{
     de::{
-        self, value::StrDeserializer, DeserializeSeed, EnumAccess, Error, IntoDeserializer,
-        MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
+        self, value::StrDeserializer, DeserializeOwned, DeserializeSeed, EnumAccess, Error,
+        IntoDeserializer, MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
     },
     forward_to_deserialize_any,
 };
 use std::{
-    collections::hash_map::{Keys, Values},
-    convert::TryInto,
-    iter::{Enumerate, Peekable},
+    cell::RefCell,
+    collections::hash_map,
+    convert::{TryFrom, TryInto},
+    iter::Enumerate,
     slice::Iter,
+    vec,
 };
 
-impl<'de> de::Deserializer<'de> for &'de ConfigurationTree {
-    type Error = ConfigurationError;
+/// Deserializes `node` into `T`, matching its keys with fields of structs and variants of enums according to `mode`.
+pub(crate) fn deserialize<'de, T: DeserializeOwned>(
+    node: impl Into<Node<'de>>,
+    mode: KeyMode,
+) -> Result<T, ConfigurationError> {
+    let node = node.into();
+    if mode == KeyMode::Exact {
+        return T::deserialize(TreeDeserializer {
+            node,
+            context: None,
+        });
+    }
 
-    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        match self {
-            ConfigurationTree::Value(vt) => match vt {
-                Some(Value::Float(v)) => visitor.visit_f64(*v),
-                Some(Value::String(v)) => visitor.visit_string(v.clone()),
-                Some(Value::SignedInteger(v)) => visitor.visit_i64(*v),
-                Some(Value::Bool(v)) => visitor.visit_bool(*v),
-                None => visitor.visit_none(),
-            },
-            ConfigurationTree::Map(m) => {
-                visitor.visit_map(MapAccessor(m.keys().peekable(), m.values()))
+    let context = Context::new(mode);
+    T::deserialize(TreeDeserializer {
+        node,
+        context: Some(&context),
+    })
+}
+
+/// Deserializes `node` into `T`, reporting all errors instead of the first one.
+///
+/// Errors found while reading nodes, like values of wrong type or unknown enum variants, are recorded in a single pass,
+/// and failing nodes are replaced with placeholders deserializing into zero value of requested type.
+/// Errors raised by deserialized types themselves, like missing fields or failed validation, abort deserialization
+/// and serde offers no way to resume it. Deserialization is then repeated with a placeholder at path of such error,
+/// so it runs once more for each of them.
+pub(crate) fn deserialize_reporting_all<'de, T: DeserializeOwned>(
+    node: impl Into<Node<'de>>,
+    mode: KeyMode,
+) -> Result<T, Vec<ConfigurationError>> {
+    let node = node.into();
+    let mut placeholders: Vec<Vec<Key>> = Vec::new();
+    let mut errors: Vec<ConfigurationError> = Vec::new();
+
+    loop {
+        let context = Context {
+            placeholders,
+            errors: Some(RefCell::new(Vec::new())),
+            ..Context::new(mode)
+        };
+        let result = T::deserialize(TreeDeserializer {
+            node,
+            context: Some(&context),
+        });
+        placeholders = context.placeholders;
+
+        // errors recorded before an aborted attempt are recorded again by the next one
+        for error in context.errors.map(RefCell::into_inner).unwrap_or_default() {
+            if !errors.iter().any(|e| e.get_path() == error.get_path()) {
+                errors.push(error);
+            }
+        }
+
+        match result {
+            Ok(value) if errors.is_empty() => return Ok(value),
+            Ok(_) => return Err(errors),
+            Err(error) => {
+                // paths of errors are stored starting from the innermost key
+                let path: Vec<Key> = error
+                    .get_path()
+                    .map(|p| p.iter().rev().cloned().collect())
+                    .unwrap_or_default();
+
+                // errors of root cannot be recovered from
+                if path.is_empty() {
+                    errors.push(error);
+                    return Err(errors);
+                }
+
+                // placeholders themselves might be rejected, e.g. by types not accepting zero,
+                // such errors are not reported as they follow from errors already recorded
+                if placeholders.iter().any(|p| path.starts_with(p)) {
+                    return Err(errors);
+                }
+                if !errors.iter().any(|e| e.get_path() == error.get_path()) {
+                    errors.push(error);
+                }
+                placeholders.push(path);
             }
-            ConfigurationTree::Array(a) => visitor.visit_seq(SeqAccessor(a.iter().enumerate())),
         }
     }
+}
 
-    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_bool(TryInto::try_into(self)?)
+/// Deserializes `node` into `T`, reporting keys that were not used by it.
+///
+/// Unknown keys are reported as [`UnknownKey`](ErrorCode::UnknownKey) errors, in order they were encountered.
+pub(crate) fn deserialize_reporting_unknown<'de, T: DeserializeOwned>(
+    node: impl Into<Node<'de>>,
+    mode: KeyMode,
+) -> Result<(T, Vec<ConfigurationError>), ConfigurationError> {
+    let context = Context {
+        unknown: Some(RefCell::new(Vec::new())),
+        ..Context::new(mode)
+    };
+
+    let value = T::deserialize(TreeDeserializer {
+        node: node.into(),
+        context: Some(&context),
+    })?;
+
+    let unknown = context
+        .unknown
+        .map(|unknown| unknown.into_inner())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|path| {
+            let key = path.last().map(|k| k.to_string()).unwrap_or_default();
+            let error: ConfigurationError = ErrorCode::UnknownKey(key).into();
+            error.enrich_with_keys(&path.into())
+        })
+        .collect();
+
+    Ok((value, unknown))
+}
+
+/// Turns unknown keys reported during conversion into `T` into an error.
+pub(crate) fn deny_unknown<T>(
+    value: T,
+    unknown: Vec<ConfigurationError>,
+) -> Result<T, ConfigurationError> {
+    if unknown.is_empty() {
+        Ok(value)
+    } else {
+        Err(ConfigurationError::combine(unknown).enrich_with_context(conversion_context::<T>()))
     }
+}
 
-    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_i8(TryInto::try_into(self)?)
+/// Returns message put in context of errors of conversion into `T`.
+pub(crate) fn conversion_context<T>() -> String {
+    format!(
+        "Failed to deserialize configuration to type {}",
+        std::any::type_name::<T>()
+    )
+}
+
+/// State shared by all deserializers of a single tree.
+///
+/// It is only present when deserialization reports more than the first error or keys are not compared exactly.
+struct Context {
+    // paths counting from root
+    placeholders: Vec<Vec<Key>>,
+    path: RefCell<Vec<Key>>,
+    // errors of nodes replaced with placeholders, if all errors are reported
+    errors: Option<RefCell<Vec<ConfigurationError>>>,
+    // paths of ignored values, if they are tracked
+    unknown: Option<RefCell<Vec<Vec<Key>>>>,
+    key_mode: KeyMode,
+}
+
+impl Context {
+    fn new(key_mode: KeyMode) -> Self {
+        Context {
+            placeholders: Vec::new(),
+            path: RefCell::new(Vec::new()),
+            errors: None,
+            unknown: None,
+            key_mode,
+        }
     }
 
-    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_i16(TryInto::try_into(self)?)
+    // keys of maps are already normalized, so they are matched against normalized names of fields or variants
+    fn matching<'a>(&self, key: &'a str, names: &'static [&'static str]) -> &'a str {
+        if self.key_mode == KeyMode::Exact {
+            return key;
+        }
+
+        names
+            .iter()
+            .find(|name| self.key_mode.normalize(name) == key)
+            .copied()
+            .unwrap_or(key)
     }
 
-    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_i32(TryInto::try_into(self)?)
+    fn is_placeholder(&self, path: &[Key]) -> bool {
+        self.placeholders.iter().any(|p| p == path)
     }
 
-    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_i64(TryInto::try_into(self)?)
+    fn missing_fields(&self, map: Node<'_>, fields: &'static [&'static str]) -> Vec<&'static str> {
+        let mut path = self.path.borrow().clone();
+        fields
+            .iter()
+            .filter(|field| map.get(self.key_mode.normalize(field).as_ref()).is_none())
+            .filter(|field| {
+                path.push(Key::Map((**field).to_owned()));
+                let result = self.is_placeholder(&path);
+                path.pop();
+                result
+            })
+            .copied()
+            .collect()
     }
 
-    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_i8(TryInto::try_into(self)?)
+    // returns whether ignored value was recorded
+    fn ignore(&self) -> bool {
+        match self.unknown {
+            Some(ref unknown) => {
+                unknown.borrow_mut().push(self.path.borrow().clone());
+                true
+            }
+            None => false,
+        }
     }
 
-    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_i16(TryInto::try_into(self)?)
+    // serde reports missing fields without a path, it is added to let deserialization be repeated with a placeholder
+    fn locate_missing_field(
+        &self,
+        error: ConfigurationError,
+        fields: &'static [&'static str],
+    ) -> ConfigurationError {
+        let field = match error.get_code() {
+            ErrorCode::DeserializationError(message) if error.get_path().is_none() => fields
+                .iter()
+                .find(|field| *message == format!("missing field `{}`", field)),
+            _ => None,
+        };
+
+        match field {
+            Some(field) => error.enrich_with_key(Key::Map((*field).to_owned())),
+            None => error,
+        }
     }
 
-    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_i32(TryInto::try_into(self)?)
+    fn enter<R>(&self, key: Key, func: impl FnOnce(bool) -> R) -> R {
+        self.path.borrow_mut().push(key);
+        let placeholder = self.is_placeholder(&self.path.borrow());
+        let result = func(placeholder);
+        self.path.borrow_mut().pop();
+        result
     }
+}
 
-    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_i64(TryInto::try_into(self)?)
+// records error of the current node if all errors are reported, returns it back otherwise
+fn record(context: Option<&Context>, error: ConfigurationError) -> Result<(), ConfigurationError> {
+    match context.and_then(|c| c.errors.as_ref().map(|errors| (c, errors))) {
+        Some((context, errors)) => {
+            let path = context.path.borrow().clone();
+            errors
+                .borrow_mut()
+                .push(error.enrich_with_keys(&path.into()));
+            Ok(())
+        }
+        None => Err(error),
+    }
+}
+
+/// Node being deserialized, either stored in a tree or merged from trees of multiple configurations.
+///
+/// Merged nodes keep borrowing subtrees taken over from a single tree, so values are never merged
+/// and only maps and arrays combining multiple trees are created.
+#[derive(Clone, Copy)]
+pub(crate) enum Node<'de> {
+    Tree(&'de ConfigurationTree),
+    Merged(&'de MergedTree<'de>),
+}
+
+// contents of a node, regardless of whether it was merged
+enum Shape<'de> {
+    Value(&'de Option<Value>),
+    Map(Entries<'de>),
+    Array(Elements<'de>),
+}
+
+impl<'de> Node<'de> {
+    fn shape(self) -> Shape<'de> {
+        match self {
+            Node::Tree(ConfigurationTree::Value(v)) => Shape::Value(v),
+            Node::Tree(ConfigurationTree::Map(m)) => Shape::Map(Entries::Tree(m.iter())),
+            Node::Tree(ConfigurationTree::Array(a)) => Shape::Array(Elements::Tree(a.iter())),
+            Node::Merged(MergedTree::Borrowed(node)) => Node::Tree(node).shape(),
+            Node::Merged(MergedTree::Map(m)) => Shape::Map(Entries::Merged(m.iter())),
+            Node::Merged(MergedTree::Array(a)) => Shape::Array(Elements::Merged(a.iter())),
+        }
+    }
+
+    fn own_type(self) -> NodeType {
+        match self {
+            Node::Tree(node) => node.own_type(),
+            Node::Merged(node) => node.own_type(),
+        }
     }
 
-    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    fn get(self, key: &str) -> Option<Node<'de>> {
+        match self {
+            Node::Tree(ConfigurationTree::Map(m)) => m.get(key).map(Node::Tree),
+            Node::Merged(MergedTree::Borrowed(node)) => Node::Tree(node).get(key),
+            Node::Merged(MergedTree::Map(m)) => m.get(key).map(Node::from),
+            _ => None,
+        }
+    }
+
+    fn convert<T>(self) -> Result<T, ConfigurationError>
     where
-        V: Visitor<'de>,
+        T: TryFrom<&'de ConfigurationTree, Error = ConfigurationError>,
     {
-        visitor.visit_f32(TryInto::try_into(self)?)
+        match self {
+            Node::Tree(node) => node.try_into(),
+            Node::Merged(MergedTree::Borrowed(node)) => Node::Tree(node).convert(),
+            Node::Merged(node) => {
+                Err(ErrorCode::WrongNodeType(NodeType::Value, node.own_type()).into())
+            }
+        }
     }
+}
+
+impl<'de> From<&'de ConfigurationTree> for Node<'de> {
+    fn from(node: &'de ConfigurationTree) -> Self {
+        Node::Tree(node)
+    }
+}
+
+impl<'de, 'config: 'de> From<&'de MergedTree<'config>> for Node<'de> {
+    fn from(node: &'de MergedTree<'config>) -> Self {
+        match node {
+            MergedTree::Borrowed(node) => Node::Tree(node),
+            merged => Node::Merged(merged),
+        }
+    }
+}
+
+enum Entries<'de> {
+    Tree(hash_map::Iter<'de, String, ConfigurationTree>),
+    Merged(hash_map::Iter<'de, &'de str, MergedTree<'de>>),
+}
+
+impl<'de> Iterator for Entries<'de> {
+    type Item = (&'de str, Node<'de>);
 
-    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Entries::Tree(entries) => entries.next().map(|(k, v)| (k.as_str(), Node::Tree(v))),
+            Entries::Merged(entries) => entries.next().map(|(k, v)| (*k, Node::from(v))),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Entries::Tree(entries) => entries.size_hint(),
+            Entries::Merged(entries) => entries.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for Entries<'_> {}
+
+enum Elements<'de> {
+    Tree(Iter<'de, ConfigurationTree>),
+    Merged(Iter<'de, MergedTree<'de>>),
+}
+
+impl<'de> Iterator for Elements<'de> {
+    type Item = Node<'de>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Elements::Tree(elements) => elements.next().map(Node::Tree),
+            Elements::Merged(elements) => elements.next().map(Node::from),
+        }
+    }
+}
+
+fn deserialize_child<'de, S, K>(
+    seed: S,
+    key: K,
+    node: Option<Node<'de>>,
+    context: Option<&'de Context>,
+) -> Result<S::Value, ConfigurationError>
+where
+    S: DeserializeSeed<'de>,
+    K: Fn() -> Key,
+{
+    let result = match (context, node) {
+        (None, Some(node)) => seed.deserialize(TreeDeserializer {
+            node,
+            context: None,
+        }),
+        (Some(context), node) => context.enter(key(), |placeholder| match node {
+            Some(node) if !placeholder => seed.deserialize(TreeDeserializer {
+                node,
+                context: Some(context),
+            }),
+            _ => seed.deserialize(Placeholder),
+        }),
+        (None, None) => Err(Error::custom("missing value")),
+    };
+
+    result.map_err(|e| e.enrich_with_key(key()))
+}
+
+#[derive(Clone, Copy)]
+struct TreeDeserializer<'de> {
+    node: Node<'de>,
+    context: Option<&'de Context>,
+}
+
+impl<'de> TreeDeserializer<'de> {
+    fn map_accessor(
+        &self,
+        entries: Entries<'de>,
+        fields: &'static [&'static str],
+        missing: Vec<&'static str>,
+    ) -> MapAccessor<'de> {
+        MapAccessor {
+            entries,
+            fields,
+            missing: missing.into_iter(),
+            pending: None,
+            context: self.context,
+        }
+    }
+
+    fn seq_accessor(&self, elements: Elements<'de>) -> SeqAccessor<'de> {
+        SeqAccessor {
+            elements: elements.enumerate(),
+            context: self.context,
+        }
+    }
+
+    fn reports_all(&self) -> bool {
+        self.context.map_or(false, |c| c.errors.is_some())
+    }
+
+    fn unexpected(&self) -> Unexpected<'de> {
+        match self.node.shape() {
+            Shape::Value(Some(Value::Float(v))) => Unexpected::Float(*v),
+            Shape::Value(Some(Value::String(v))) => Unexpected::Str(v),
+            Shape::Value(Some(Value::SignedInteger(v))) => Unexpected::Signed(*v),
+            Shape::Value(Some(Value::Bool(v))) => Unexpected::Bool(*v),
+            Shape::Value(None) => Unexpected::Option,
+            Shape::Map(_) => Unexpected::Map,
+            Shape::Array(_) => Unexpected::Seq,
+        }
+    }
+
+    // when all errors are reported, error of this node is recorded and it is deserialized from a placeholder instead
+    fn recover<V, F>(
+        &self,
+        error: ConfigurationError,
+        visitor: V,
+        placeholder: F,
+    ) -> Result<V::Value, ConfigurationError>
     where
         V: Visitor<'de>,
+        F: FnOnce(Placeholder, V) -> Result<V::Value, ConfigurationError>,
     {
-        visitor.visit_f64(TryInto::try_into(self)?)
+        record(self.context, error)?;
+        placeholder(Placeholder, visitor)
     }
+}
 
-    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+macro_rules! deserialize_converted {
+    ($($method:ident => $visit:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self.node.convert() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(error) => {
+                        self.recover(error, visitor, |p, v| de::Deserializer::$method(p, v))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! forward_to_tree_deserializer {
+    ($($method:ident($($arg:ident : $ty:ty),*))*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                TreeDeserializer {
+                    node: self.into(),
+                    context: None,
+                }
+                .$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+macro_rules! impl_tree_deserializer {
+    ($($ty:ty),*) => {
+        $(impl<'de> de::Deserializer<'de> for $ty {
+            type Error = ConfigurationError;
+
+            forward_to_tree_deserializer! {
+                deserialize_any() deserialize_bool()
+                deserialize_i8() deserialize_i16() deserialize_i32() deserialize_i64()
+                deserialize_u8() deserialize_u16() deserialize_u32() deserialize_u64()
+                deserialize_f32() deserialize_f64() deserialize_char()
+                deserialize_str() deserialize_string() deserialize_bytes() deserialize_byte_buf()
+                deserialize_option() deserialize_unit()
+                deserialize_unit_struct(name: &'static str)
+                deserialize_newtype_struct(name: &'static str)
+                deserialize_seq() deserialize_tuple(len: usize)
+                deserialize_tuple_struct(name: &'static str, len: usize)
+                deserialize_map()
+                deserialize_struct(name: &'static str, fields: &'static [&'static str])
+                deserialize_enum(name: &'static str, variants: &'static [&'static str])
+                deserialize_identifier() deserialize_ignored_any()
+            }
+        })*
+    };
+}
+
+// merged trees are deserialized in place, so subtrees borrowed from configurations are never cloned
+impl_tree_deserializer!(&'de ConfigurationTree, &'de MergedTree<'de>);
+
+impl<'de> de::Deserializer<'de> for TreeDeserializer<'de> {
+    type Error = ConfigurationError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        let self_string: String = TryInto::try_into(self)?;
-        let characters: Vec<char> = self_string.chars().collect();
-        if characters.len() == 1 {
-            visitor.visit_char(characters[0])
-        } else {
-            Err(Error::invalid_length(
-                characters.len(),
-                &"string of length 1",
-            ))
+        match self.node.shape() {
+            Shape::Value(vt) => match vt {
+                Some(Value::Float(v)) => visitor.visit_f64(*v),
+                Some(Value::String(v)) => visitor.visit_string(v.clone()),
+                Some(Value::SignedInteger(v)) => visitor.visit_i64(*v),
+                Some(Value::Bool(v)) => visitor.visit_bool(*v),
+                None => visitor.visit_none(),
+            },
+            Shape::Map(entries) => visitor.visit_map(self.map_accessor(entries, &[], Vec::new())),
+            Shape::Array(elements) => visitor.visit_seq(self.seq_accessor(elements)),
         }
     }
 
-    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_borrowed_str(TryInto::try_into(self)?)
+    deserialize_converted! {
+        deserialize_bool => visit_bool
+        deserialize_i8 => visit_i8
+        deserialize_i16 => visit_i16
+        deserialize_i32 => visit_i32
+        deserialize_i64 => visit_i64
+        deserialize_u8 => visit_i8
+        deserialize_u16 => visit_i16
+        deserialize_u32 => visit_i32
+        deserialize_u64 => visit_i64
+        deserialize_f32 => visit_f32
+        deserialize_f64 => visit_f64
+        deserialize_str => visit_borrowed_str
+        deserialize_string => visit_string
     }
 
-    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_string(TryInto::try_into(self)?)
+        let character = self.node.convert::<String>().and_then(|self_string| {
+            let characters: Vec<char> = self_string.chars().collect();
+            if characters.len() == 1 {
+                Ok(characters[0])
+            } else {
+                Err(Error::invalid_length(
+                    characters.len(),
+                    &"string of length 1",
+                ))
+            }
+        });
+
+        match character {
+            Ok(character) => visitor.visit_char(character),
+            Err(error) => self.recover(error, visitor, |p, v| {
+                de::Deserializer::deserialize_char(p, v)
+            }),
+        }
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self {
-            ConfigurationTree::Value(Some(_)) => visitor.visit_some(self),
-            ConfigurationTree::Value(None) => visitor.visit_none(),
-            cr => Err(Error::invalid_type(
-                Unexpected::Other(&cr.node_type().to_string()),
-                &"value",
-            )),
+        match self.node.shape() {
+            Shape::Value(Some(_)) => visitor.visit_some(self),
+            Shape::Value(None) => visitor.visit_none(),
+            _ => self.recover(
+                Error::invalid_type(
+                    Unexpected::Other(&self.node.own_type().to_string()),
+                    &"value",
+                ),
+                visitor,
+                |p, v| de::Deserializer::deserialize_option(p, v),
+            ),
         }
     }
 
@@ -163,26 +613,26 @@ impl<'de> de::Deserializer<'de> for &'de ConfigurationTree {
     where
         V: Visitor<'de>,
     {
-        match self {
-            ConfigurationTree::Value(Some(Value::String(s))) => {
+        let error = match self.node.shape() {
+            Shape::Value(Some(Value::String(s))) => {
                 if s.trim().is_empty() {
-                    visitor.visit_unit()
-                } else {
-                    Err(Error::custom(
-                        "value should be null or empty to deserialize unit",
-                    ))
+                    return visitor.visit_unit();
                 }
+                Error::custom("value should be null or empty to deserialize unit")
             }
-            ConfigurationTree::Value(None) => visitor.visit_unit(),
-            ConfigurationTree::Value(_) => Err(Error::invalid_type(
-                Unexpected::Other("non empty value"),
-                &"null or empty value",
-            )),
-            cr => Err(Error::invalid_type(
-                Unexpected::Other(&cr.node_type().to_string()),
+            Shape::Value(None) => return visitor.visit_unit(),
+            Shape::Value(_) => {
+                Error::invalid_type(Unexpected::Other("non empty value"), &"null or empty value")
+            }
+            _ => Error::invalid_type(
+                Unexpected::Other(&self.node.own_type().to_string()),
                 &"null or empty value",
-            )),
-        }
+            ),
+        };
+
+        self.recover(error, visitor, |p, v| {
+            de::Deserializer::deserialize_unit(p, v)
+        })
     }
 
     fn deserialize_unit_struct<V>(
@@ -207,25 +657,110 @@ impl<'de> de::Deserializer<'de> for &'de ConfigurationTree {
         visitor.visit_newtype_struct(self)
     }
 
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.node.shape() {
+            Shape::Array(_) => self.deserialize_any(visitor),
+            // visitors would reject other nodes, it is done upfront to deserialize them from placeholders
+            _ if self.reports_all() => self.recover(
+                Error::invalid_type(self.unexpected(), &visitor),
+                visitor,
+                |p, v| de::Deserializer::deserialize_seq(p, v),
+            ),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.node.shape() {
+            Shape::Map(_) => self.deserialize_any(visitor),
+            _ if self.reports_all() => self.recover(
+                Error::invalid_type(self.unexpected(), &visitor),
+                visitor,
+                |p, v| de::Deserializer::deserialize_map(p, v),
+            ),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match (self.node.shape(), self.context) {
+            // fields reported as missing in previous attempts are filled with placeholders
+            (Shape::Map(entries), Some(context)) => {
+                let result = visitor.visit_map(self.map_accessor(
+                    entries,
+                    fields,
+                    context.missing_fields(self.node, fields),
+                ));
+                if self.reports_all() {
+                    result.map_err(|e| context.locate_missing_field(e, fields))
+                } else {
+                    result
+                }
+            }
+            _ if self.reports_all() => self.recover(
+                Error::invalid_type(self.unexpected(), &visitor),
+                visitor,
+                |p, v| de::Deserializer::deserialize_struct(p, name, fields, v),
+            ),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
     fn deserialize_enum<V>(
         self,
         _name: &'static str,
-        _variants: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_enum(EnumAccessor { root: self })
+        visitor.visit_enum(EnumAccessor {
+            root: self.node,
+            variants,
+            variant: None,
+            placeholder: false,
+            context: self.context,
+        })
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // values are ignored when no field of deserialized type corresponds to them
+        match self.context {
+            Some(context) if context.ignore() => visitor.visit_unit(),
+            _ => self.deserialize_any(visitor),
+        }
     }
 
-    forward_to_deserialize_any!(bytes byte_buf seq map tuple tuple_struct struct identifier ignored_any);
+    forward_to_deserialize_any!(bytes byte_buf tuple tuple_struct identifier);
 }
 
-struct MapAccessor<'conf>(
-    Peekable<Keys<'conf, String, ConfigurationTree>>,
-    Values<'conf, String, ConfigurationTree>,
-);
+struct MapAccessor<'conf> {
+    entries: Entries<'conf>,
+    // fields of deserialized struct, empty for other maps
+    fields: &'static [&'static str],
+    // fields absent from the map which are deserialized from placeholders
+    missing: vec::IntoIter<&'static str>,
+    pending: Option<(&'conf str, Option<Node<'conf>>)>,
+    context: Option<&'conf Context>,
+}
 
 impl<'de> MapAccess<'de> for MapAccessor<'de> {
     type Error = ConfigurationError;
@@ -234,13 +769,22 @@ impl<'de> MapAccess<'de> for MapAccessor<'de> {
     where
         K: DeserializeSeed<'de>,
     {
-        match self.0.peek() {
-            Some(v) => {
-                let deserializer: StrDeserializer<ConfigurationError> =
-                    v.as_str().into_deserializer();
+        let entry = match self.entries.next() {
+            Some((key, node)) => Some((key, Some(node))),
+            None => self.missing.next().map(|field| (field, None)),
+        };
+
+        match entry {
+            Some((k, node)) => {
+                self.pending = Some((k, node));
+                let name = match self.context {
+                    Some(context) => context.matching(k, self.fields),
+                    None => k,
+                };
+                let deserializer: StrDeserializer<ConfigurationError> = name.into_deserializer();
                 let key = seed
                     .deserialize(deserializer)
-                    .map_err(|e| e.enrich_with_key(Key::Map((*v).to_owned())))?;
+                    .map_err(|e| e.enrich_with_key(Key::Map(k.to_owned())))?;
                 Ok(Some(key))
             }
             None => Ok(None),
@@ -251,28 +795,21 @@ impl<'de> MapAccess<'de> for MapAccessor<'de> {
     where
         V: DeserializeSeed<'de>,
     {
-        let key = self.0.next();
-        if key.is_none() {
-            return Err(Error::custom(
+        match self.pending.take() {
+            Some((key, node)) => {
+                deserialize_child(seed, || Key::Map(key.to_owned()), node, self.context)
+            }
+            None => Err(Error::custom(
                 "missing key corresponding to value being deserialized in a map",
-            ));
-        }
-
-        let key = key.unwrap();
-
-        match self.1.next() {
-            Some(v) => Ok(seed
-                .deserialize(v)
-                .map_err(|e| e.enrich_with_key(Key::Map(key.to_owned())))?),
-            None => Err(Error::custom(format!(
-                "missing value corresponding to a key {} in a map",
-                key
-            ))),
+            )),
         }
     }
 }
 
-struct SeqAccessor<'conf>(Enumerate<Iter<'conf, ConfigurationTree>>);
+struct SeqAccessor<'conf> {
+    elements: Enumerate<Elements<'conf>>,
+    context: Option<&'conf Context>,
+}
 
 impl<'de> SeqAccess<'de> for SeqAccessor<'de> {
     type Error = ConfigurationError;
@@ -281,18 +818,75 @@ impl<'de> SeqAccess<'de> for SeqAccessor<'de> {
     where
         T: DeserializeSeed<'de>,
     {
-        match self.0.next() {
-            Some((index, v)) => Ok(Some(
-                seed.deserialize(v)
-                    .map_err(|e| e.enrich_with_key(Key::Array(index)))?,
-            )),
+        match self.elements.next() {
+            Some((index, v)) => Ok(Some(deserialize_child(
+                seed,
+                || Key::Array(index),
+                Some(v),
+                self.context,
+            )?)),
             None => Ok(None),
         }
     }
 }
 
 struct EnumAccessor<'conf> {
-    root: &'conf ConfigurationTree,
+    root: Node<'conf>,
+    variants: &'static [&'static str],
+    // key under which content of the variant is stored, if any
+    variant: Option<&'conf str>,
+    // whether the variant is a placeholder for an enum that failed to deserialize
+    placeholder: bool,
+    context: Option<&'conf Context>,
+}
+
+impl<'conf> EnumAccessor<'conf> {
+    fn deserializer(&self) -> TreeDeserializer<'conf> {
+        TreeDeserializer {
+            node: self.root,
+            context: self.context,
+        }
+    }
+
+    fn content<R>(
+        &self,
+        func: impl FnOnce(bool) -> Result<R, ConfigurationError>,
+    ) -> Result<R, ConfigurationError> {
+        let variant = match self.variant {
+            Some(variant) => variant,
+            None => return func(self.placeholder),
+        };
+
+        let result = match self.context {
+            Some(context) => context.enter(Key::Map(variant.to_owned()), func),
+            None => func(false),
+        };
+
+        result.map_err(|e| e.enrich_with_key(Key::Map(variant.to_owned())))
+    }
+
+    // when all errors are reported, enums that failed to deserialize take the first variant
+    fn recover<V>(
+        mut self,
+        error: ConfigurationError,
+        seed: V,
+    ) -> Result<(V::Value, Self), ConfigurationError>
+    where
+        V: DeserializeSeed<'conf>,
+    {
+        record(self.context, error)?;
+
+        let first = match self.variants.first() {
+            Some(first) => *first,
+            None => return Err(Error::custom("enum without variants")),
+        };
+        let deserializer: StrDeserializer<ConfigurationError> = first.into_deserializer();
+        let value = seed.deserialize(deserializer)?;
+
+        self.variant = None;
+        self.placeholder = true;
+        Ok((value, self))
+    }
 }
 
 impl<'de> EnumAccess<'de> for EnumAccessor<'de> {
@@ -303,34 +897,55 @@ impl<'de> EnumAccess<'de> for EnumAccessor<'de> {
     where
         V: DeserializeSeed<'de>,
     {
-        match self.root {
-            ConfigurationTree::Value(Some(Value::String(v))) => {
+        let reports_all = self.deserializer().reports_all();
+        let variants = self.variants;
+
+        match self.root.shape() {
+            Shape::Value(Some(Value::String(v))) => {
+                // seed would reject unknown variant, it is done upfront to recover from it
+                if reports_all && !self.variants.contains(&v.as_str()) {
+                    return self.recover(Error::unknown_variant(v, variants), seed);
+                }
+
                 let deserializer: StrDeserializer<ConfigurationError> =
                     v.as_str().into_deserializer();
                 let value = seed.deserialize(deserializer)?;
 
                 Ok((value, self))
             }
-            ConfigurationTree::Value(_) => Err(Error::custom(
-                "expected string or single key map, got other value type",
-            )),
-            ConfigurationTree::Map(m) => {
-                if m.len() != 1 {
-                    return Err(Error::invalid_length(m.len(), &"expected map of length 1"));
+            Shape::Value(_) => self.recover(
+                Error::custom("expected string or single key map, got other value type"),
+                seed,
+            ),
+            Shape::Map(mut entries) => {
+                if entries.len() != 1 {
+                    return self.recover(
+                        Error::invalid_length(entries.len(), &"expected map of length 1"),
+                        seed,
+                    );
                 }
 
-                let key = m.keys().next().unwrap().as_str();
-                let deserializer: StrDeserializer<ConfigurationError> = key.into_deserializer();
+                let (key, content) = entries.next().unwrap(); // safe due to previous check
+                let name = match self.context {
+                    Some(context) => context.matching(key, self.variants),
+                    None => key,
+                };
+                if reports_all && !self.variants.contains(&name) {
+                    return self.recover(Error::unknown_variant(name, variants), seed);
+                }
+
+                let deserializer: StrDeserializer<ConfigurationError> = name.into_deserializer();
                 let value = seed.deserialize(deserializer)?;
 
-                self.root = m.get(key).unwrap(); // safe due to previous check;
+                self.root = content;
+                self.variant = Some(key);
 
                 Ok((value, self))
             }
-            ConfigurationTree::Array(_) => Err(Error::invalid_type(
-                Unexpected::Seq,
-                &"expected string or single key map",
-            )),
+            Shape::Array(_) => self.recover(
+                Error::invalid_type(Unexpected::Seq, &"expected string or single key map"),
+                seed,
+            ),
         }
     }
 }
@@ -346,45 +961,306 @@ impl<'de> VariantAccess<'de> for EnumAccessor<'de> {
     where
         T: DeserializeSeed<'de>,
     {
-        match self.root {
-            ConfigurationTree::Value(Some(tv)) => seed.deserialize(tv),
-            cr => Err(Error::custom(format!(
-                "expected value, got {}",
-                cr.node_type(),
-            ))),
-        }
+        self.content(|placeholder| match self.root.shape() {
+            _ if placeholder => seed.deserialize(Placeholder),
+            Shape::Value(Some(tv)) => seed.deserialize(tv),
+            _ => {
+                record(
+                    self.context,
+                    Error::custom(format!("expected value, got {}", self.root.own_type())),
+                )?;
+                seed.deserialize(Placeholder)
+            }
+        })
     }
 
-    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.root {
-            ConfigurationTree::Array(a) => visitor.visit_seq(SeqAccessor(a.iter().enumerate())),
-            cr => Err(Error::custom(format!(
-                "expected array, got {}",
-                cr.node_type(),
-            ))),
-        }
+        self.content(|placeholder| match self.root.shape() {
+            _ if placeholder => de::Deserializer::deserialize_tuple(Placeholder, len, visitor),
+            Shape::Array(elements) => visitor.visit_seq(self.deserializer().seq_accessor(elements)),
+            _ => {
+                record(
+                    self.context,
+                    Error::custom(format!("expected array, got {}", self.root.own_type())),
+                )?;
+                de::Deserializer::deserialize_tuple(Placeholder, len, visitor)
+            }
+        })
     }
 
     fn struct_variant<V>(
         self,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.root {
-            ConfigurationTree::Map(m) => {
-                visitor.visit_map(MapAccessor(m.keys().peekable(), m.values()))
+        self.content(|placeholder| match self.root.shape() {
+            _ if placeholder => {
+                de::Deserializer::deserialize_struct(Placeholder, "", fields, visitor)
+            }
+            Shape::Map(_) => {
+                de::Deserializer::deserialize_struct(self.deserializer(), "", fields, visitor)
             }
-            cr => Err(Error::custom(format!(
-                "expected map, got {}",
-                cr.node_type(),
-            ))),
+            _ => {
+                record(
+                    self.context,
+                    Error::custom(format!("expected map, got {}", self.root.own_type())),
+                )?;
+                de::Deserializer::deserialize_struct(Placeholder, "", fields, visitor)
+            }
+        })
+    }
+}
+
+/// Stands in for nodes that failed to deserialize when all errors are reported.
+///
+/// It deserializes into zero value of requested type, i.e. `0`, `false`, empty string or collection.
+/// Structs are filled with placeholders and enums take their first variant.
+struct Placeholder;
+
+macro_rules! deserialize_placeholder {
+    ($($method:ident => $visit:ident($value:expr))*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit($value)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Placeholder {
+    type Error = ConfigurationError;
+
+    deserialize_placeholder! {
+        deserialize_bool => visit_bool(false)
+        deserialize_i8 => visit_i8(0)
+        deserialize_i16 => visit_i16(0)
+        deserialize_i32 => visit_i32(0)
+        deserialize_i64 => visit_i64(0)
+        deserialize_u8 => visit_u8(0)
+        deserialize_u16 => visit_u16(0)
+        deserialize_u32 => visit_u32(0)
+        deserialize_u64 => visit_u64(0)
+        deserialize_f32 => visit_f32(0.0)
+        deserialize_f64 => visit_f64(0.0)
+        deserialize_char => visit_char('\0')
+        deserialize_str => visit_str("")
+        deserialize_string => visit_string(String::new())
+        deserialize_identifier => visit_str("")
+        deserialize_bytes => visit_bytes(&[])
+        deserialize_byte_buf => visit_byte_buf(Vec::new())
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(PlaceholderAccessor { remaining: 0 })
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(PlaceholderAccessor { remaining: len })
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(PlaceholderFields([].iter()))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(PlaceholderFields(fields.iter()))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match variants.first() {
+            Some(variant) => visitor.visit_enum(PlaceholderVariant(variant)),
+            None => Err(Error::custom("enum without variants")),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
+struct PlaceholderAccessor {
+    remaining: usize,
+}
+
+impl<'de> SeqAccess<'de> for PlaceholderAccessor {
+    type Error = ConfigurationError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
         }
+
+        self.remaining -= 1;
+        seed.deserialize(Placeholder).map(Some)
+    }
+}
+
+struct PlaceholderFields(Iter<'static, &'static str>);
+
+impl<'de> MapAccess<'de> for PlaceholderFields {
+    type Error = ConfigurationError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.0.next() {
+            Some(field) => {
+                let deserializer: StrDeserializer<ConfigurationError> = field.into_deserializer();
+                seed.deserialize(deserializer).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(Placeholder)
+    }
+}
+
+struct PlaceholderVariant(&'static str);
+
+impl<'de> EnumAccess<'de> for PlaceholderVariant {
+    type Error = ConfigurationError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let deserializer: StrDeserializer<ConfigurationError> = self.0.into_deserializer();
+        Ok((seed.deserialize(deserializer)?, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for PlaceholderVariant {
+    type Error = ConfigurationError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(Placeholder)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(Placeholder, len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(Placeholder, "", fields, visitor)
     }
 }
 
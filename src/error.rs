@@ -1,5 +1,5 @@
 use crate::configuration::{CompoundKey, Key, NodeType};
-use serde::de;
+use serde::{de, ser};
 use std::{convert::From, fmt::Display, ops::Deref};
 
 /// Represents all errors that might occur at all stages of processing configuration.
@@ -14,10 +14,69 @@ pub struct ErrorImpl {
     code: ErrorCode,
     context: Option<Vec<String>>,
     path: Option<Vec<Key>>,
+    location: Option<SourceLocation>,
+}
+
+/// Location in configuration source at which value involved in error was defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    source: String,
+    line: usize,
+    column: usize,
+    excerpt: Option<String>,
+}
+
+impl SourceLocation {
+    /// Creates new `SourceLocation` pointing at `line` and `column`, counted from 1, of `source`.
+    ///
+    /// Source is usually a file path, as described by [`ConfigurationInfo`](crate::configuration::ConfigurationInfo).
+    pub fn new<T: Into<String>>(source: T, line: usize, column: usize) -> Self {
+        SourceLocation {
+            source: source.into(),
+            line,
+            column,
+            excerpt: None,
+        }
+    }
+
+    /// Attaches content of the located line, displayed by [`pretty_display`](ConfigurationError::pretty_display).
+    pub fn with_excerpt<T: Into<String>>(mut self, excerpt: T) -> Self {
+        self.excerpt = Some(excerpt.into());
+        self
+    }
+
+    /// Returns description of source.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns line number.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns column number.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns content of the located line, if available.
+    pub fn excerpt(&self) -> Option<&str> {
+        self.excerpt.as_deref()
+    }
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.source, self.line, self.column)
+    }
 }
 
 /// Describes underlying cause of error.
+///
+/// New variants might be added in the future, so matching on it has to include a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ErrorCode {
     /// Informs that operation is not valid for given node type e.g descending into value node.
     WrongNodeType(NodeType, NodeType),
@@ -39,12 +98,22 @@ pub enum ErrorCode {
     /// Informs about errors during deserialization.
     /// It covers both external sources and internal structures deserialization.
     DeserializationError(String),
+    /// Informs about errors during serialization of values into configuration trees.
+    SerializationError(String),
     /// Informs about error attributable to executing invalid operations on empty configuration.
     EmptyConfiguration,
+    /// Informs that configuration source returned no content.
+    EmptySource,
     /// Informs about errors attributable to invalid operation on null value.
     NullValue,
     /// Informs about parsing error that occured.
     ParsingError(String),
+    /// Informs that array slice was used in place in which it can not be resolved.
+    MisplacedSlice(String),
+    /// Informs that configuration contains key which was not used during deserialization, e.g. because of a typo.
+    UnknownKey(String),
+    /// Groups errors of several failed operations, e.g. all providers that failed during build.
+    MultipleErrors(Vec<ConfigurationError>),
 }
 
 impl ConfigurationError {
@@ -105,6 +174,16 @@ impl ConfigurationError {
         self
     }
 
+    /// Enriches error with location in source at which value involved in it was defined.
+    ///
+    /// Location that was already set is not replaced, as the innermost one is the most precise.
+    pub fn enrich_with_location(mut self, location: SourceLocation) -> Self {
+        if self.inner.location.is_none() {
+            self.inner.location = Some(location);
+        }
+        self
+    }
+
     /// Enriches error context with a complex path.
     ///
     /// Used to put more contextual information in the error to facilitate debugging issues.
@@ -124,6 +203,18 @@ impl ConfigurationError {
         self
     }
 
+    /// Combines non-empty list of errors into one.
+    ///
+    /// Single error is returned as is, more of them are wrapped in [`MultipleErrors`](ErrorCode::MultipleErrors).
+    pub(crate) fn combine(mut errors: Vec<ConfigurationError>) -> Self {
+        debug_assert!(!errors.is_empty());
+        if errors.len() == 1 {
+            errors.remove(0)
+        } else {
+            ErrorCode::MultipleErrors(errors).into()
+        }
+    }
+
     /// Returns an object that displays error in pretty way.
     ///
     ///# Example
@@ -136,7 +227,7 @@ impl ConfigurationError {
     ///println!("Basic display : {}", error);
     ///println!("Pretty display : {}", error.pretty_display());
     ///```
-    pub fn pretty_display(&self) -> PrettyConfigurationDisplay {
+    pub fn pretty_display(&self) -> PrettyConfigurationDisplay<'_> {
         PrettyConfigurationDisplay(self)
     }
 }
@@ -158,6 +249,11 @@ impl ErrorImpl {
         }
     }
 
+    /// Returns location in source at which value involved in error was defined.
+    pub fn get_location(&self) -> Option<&SourceLocation> {
+        self.location.as_ref()
+    }
+
     /// Returns additional context attached to error.
     ///
     /// This information can contain, for instance, name of the file that was not found.
@@ -195,6 +291,10 @@ impl Display for ConfigurationError {
             write!(f, ". Path : {}", KeyVec(path))?;
         }
 
+        if let Some(ref location) = self.inner.location {
+            write!(f, ". Location : {}", location)?;
+        }
+
         if let Some(ref context) = self.inner.context {
             write!(f, ". Context: ")?;
             for msg in context.iter() {
@@ -219,6 +319,20 @@ impl<'e> Display for PrettyConfigurationDisplay<'e> {
             writeln!(f, "Path : {}", KeyVec(path))?;
         }
 
+        if let Some(ref location) = self.0.inner.location {
+            writeln!(f, "Location : {}", location)?;
+            if let Some(ref excerpt) = location.excerpt {
+                let number = location.line.to_string();
+                writeln!(f, "\t{} | {}", number, excerpt)?;
+                writeln!(
+                    f,
+                    "\t{} | {}^",
+                    " ".repeat(number.len()),
+                    " ".repeat(location.column.saturating_sub(1))
+                )?;
+            }
+        }
+
         if let Some(ref context) = self.0.inner.context {
             writeln!(f, "Context: ")?;
             for msg in context.iter() {
@@ -226,6 +340,15 @@ impl<'e> Display for PrettyConfigurationDisplay<'e> {
             }
         }
 
+        if let ErrorCode::MultipleErrors(ref errors) = self.0.inner.code {
+            for (i, error) in errors.iter().enumerate() {
+                writeln!(f, "Error {} of {}:", i + 1, errors.len())?;
+                for line in error.pretty_display().to_string().lines() {
+                    writeln!(f, "\t{}", line)?;
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -249,14 +372,28 @@ impl Display for ErrorCode {
             }
             ErrorCode::IoError(e) => write!(f, "I/O error occurred. {}", e),
             ErrorCode::DeserializationError(e) => write!(f, "Deserialization error occured. {}", e),
+            ErrorCode::SerializationError(e) => write!(f, "Serialization error occured. {}", e),
             ErrorCode::NullValue => write!(f, "Expected non-null value"),
             ErrorCode::EmptyConfiguration => write!(f, "Expected non-empty configuration"),
+            ErrorCode::EmptySource => write!(f, "Source returned no content"),
             ErrorCode::ParsingError(msg) => write!(f, "Parsing error. {}", msg),
+            ErrorCode::MisplacedSlice(k) => write!(
+                f,
+                "Slice {} can only be used as the last key of a lens or a deserialized value.",
+                k
+            ),
+            ErrorCode::UnknownKey(k) => {
+                write!(
+                    f,
+                    "Key {} is not used by any field of deserialized type.",
+                    k
+                )
+            }
+            ErrorCode::MultipleErrors(errors) => write!(f, "{} errors occurred.", errors.len()),
         }
     }
 }
 
-
 impl Deref for ConfigurationError {
     type Target = ErrorImpl;
 
@@ -286,6 +423,7 @@ impl From<ErrorCode> for ConfigurationError {
                 code: e,
                 context: None,
                 path: None,
+                location: None,
             }),
         }
     }
@@ -299,3 +437,12 @@ impl de::Error for ConfigurationError {
         ConfigurationError::from(ErrorCode::DeserializationError(msg.to_string()))
     }
 }
+
+impl ser::Error for ConfigurationError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        ConfigurationError::from(ErrorCode::SerializationError(msg.to_string()))
+    }
+}
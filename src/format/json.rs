@@ -1,9 +1,9 @@
 use crate::{
-    configuration::ConfigurationTree,
+    configuration::{ConfigurationTree, Key, Position, SourceMap},
     error::{ConfigurationError, ErrorCode},
     format::Format,
 };
-use std::default::Default;
+use std::{default::Default, iter::Peekable, str::Chars};
 
 /// Represents `json` data format.
 pub struct Json {}
@@ -30,4 +30,146 @@ impl Format for Json {
     fn describe(&self) -> String {
         "json".into()
     }
+
+    fn source_map(&self, input: &[u8]) -> Option<SourceMap> {
+        let text = std::str::from_utf8(input).ok()?;
+
+        let mut locator = Locator {
+            chars: text.chars().peekable(),
+            position: Position::new(1, 1),
+            source_map: SourceMap::new(text),
+        };
+        locator.value(&mut Vec::new());
+
+        Some(locator.source_map)
+    }
+}
+
+// Walks json text recording positions of map keys and array elements.
+// It gives up silently on malformed input.
+struct Locator<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: Position,
+    source_map: SourceMap,
+}
+
+impl<'a> Locator<'a> {
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.position = Position::new(self.position.line + 1, 1);
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().map_or(false, |c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn value(&mut self, path: &mut Vec<Key>) -> Option<()> {
+        self.skip_whitespace();
+        match *self.chars.peek()? {
+            '{' => self.map(path),
+            '[' => self.array(path),
+            '"' => self.string().map(|_| ()),
+            _ => {
+                while !matches!(self.chars.peek(), None | Some(',') | Some(']') | Some('}'))
+                    && !self.chars.peek()?.is_whitespace()
+                {
+                    self.bump();
+                }
+                Some(())
+            }
+        }
+    }
+
+    fn map(&mut self, path: &mut Vec<Key>) -> Option<()> {
+        self.bump();
+        loop {
+            self.skip_whitespace();
+            if *self.chars.peek()? == '}' {
+                self.bump();
+                return Some(());
+            }
+
+            let position = self.position;
+            let key = self.string()?;
+            self.skip_whitespace();
+            if self.bump()? != ':' {
+                return None;
+            }
+
+            path.push(Key::Map(key));
+            self.source_map.insert(path.clone(), position);
+            self.value(path)?;
+            path.pop();
+
+            self.skip_whitespace();
+            match self.bump()? {
+                ',' => continue,
+                '}' => return Some(()),
+                _ => return None,
+            }
+        }
+    }
+
+    fn array(&mut self, path: &mut Vec<Key>) -> Option<()> {
+        self.bump();
+        let mut index = 0;
+        loop {
+            self.skip_whitespace();
+            if *self.chars.peek()? == ']' {
+                self.bump();
+                return Some(());
+            }
+
+            path.push(Key::Array(index));
+            self.source_map.insert(path.clone(), self.position);
+            self.value(path)?;
+            path.pop();
+            index += 1;
+
+            self.skip_whitespace();
+            match self.bump()? {
+                ',' => continue,
+                ']' => return Some(()),
+                _ => return None,
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        if self.bump()? != '"' {
+            return None;
+        }
+
+        let mut result = String::new();
+        loop {
+            match self.bump()? {
+                '"' => return Some(result),
+                '\\' => match self.bump()? {
+                    'n' => result.push('\n'),
+                    't' => result.push('\t'),
+                    'r' => result.push('\r'),
+                    'b' => result.push('\u{8}'),
+                    'f' => result.push('\u{c}'),
+                    'u' => {
+                        let code: String = (0..4).filter_map(|_| self.bump()).collect();
+                        result.push(
+                            u32::from_str_radix(&code, 16)
+                                .ok()
+                                .and_then(std::char::from_u32)
+                                .unwrap_or(std::char::REPLACEMENT_CHARACTER),
+                        );
+                    }
+                    c => result.push(c),
+                },
+                c => result.push(c),
+            }
+        }
+    }
 }
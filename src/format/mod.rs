@@ -1,4 +1,7 @@
-use crate::{configuration::ConfigurationTree, error::ConfigurationError};
+use crate::{
+    configuration::{ConfigurationDefinition, ConfigurationInfo, ConfigurationTree, SourceMap},
+    error::ConfigurationError,
+};
 
 #[cfg(feature = "ini")]
 mod ini;
@@ -8,8 +11,6 @@ mod json;
 mod msgpack;
 #[cfg(feature = "serde_json5")]
 mod serde_json5;
-#[cfg(feature = "experimental_serde_ron")]
-mod serde_ron;
 #[cfg(feature = "serde_toml")]
 mod serde_toml;
 #[cfg(feature = "yaml")]
@@ -34,6 +35,48 @@ pub trait Format {
     fn transform(&self, input: Vec<u8>) -> Result<ConfigurationTree, ConfigurationError>;
     /// Describes this `Format`.
     fn describe(&self) -> String;
+    /// Maps nodes of configuration tree transformed from `input` to their positions in it.
+    ///
+    /// It is used to point at source locations in errors.
+    /// Formats that cannot provide positions return `None`, which is the default.
+    /// It must not fail nor panic on malformed input.
+    fn source_map(&self, _input: &[u8]) -> Option<SourceMap> {
+        None
+    }
+}
+
+impl Format for Box<dyn Format + Send + Sync> {
+    fn transform(&self, input: Vec<u8>) -> Result<ConfigurationTree, ConfigurationError> {
+        self.as_ref().transform(input)
+    }
+
+    fn describe(&self) -> String {
+        self.as_ref().describe()
+    }
+
+    fn source_map(&self, input: &[u8]) -> Option<SourceMap> {
+        self.as_ref().source_map(input)
+    }
+}
+
+/// Transforms `input` with `format` into configuration described by `info`, along with its source map if requested.
+pub(crate) fn transform_definition<F: Format + ?Sized>(
+    format: &F,
+    input: Vec<u8>,
+    info: ConfigurationInfo,
+    source_map: bool,
+) -> Result<ConfigurationDefinition, ConfigurationError> {
+    let source_map = if source_map {
+        format.source_map(&input)
+    } else {
+        None
+    };
+    let definition = ConfigurationDefinition::new(info, format.transform(input)?);
+
+    Ok(match source_map {
+        Some(source_map) => definition.with_source_map(source_map),
+        None => definition,
+    })
 }
 
 impl<T> Format for T
@@ -49,6 +92,35 @@ where
     }
 }
 
+/// Detects data format based on file extension.
+///
+/// Only formats enabled by feature flags can be detected. Comparison is case insensitive.
+/// Returns `None` if extension is not recognized.
+///
+///```rust
+///use miau::format;
+///
+///let format = format::from_extension("txt");
+///assert!(format.is_none());
+///```
+pub fn from_extension(extension: &str) -> Option<Box<dyn Format + Send + Sync>> {
+    match extension.to_lowercase().as_str() {
+        #[cfg(feature = "json")]
+        "json" => Some(Box::new(json())),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => Some(Box::new(yaml())),
+        #[cfg(feature = "serde_toml")]
+        "toml" => Some(Box::new(toml())),
+        #[cfg(feature = "serde_json5")]
+        "json5" => Some(Box::new(json5())),
+        #[cfg(feature = "msgpack")]
+        "msgpack" | "mp" => Some(Box::new(msgpack())),
+        #[cfg(feature = "ini")]
+        "ini" => Some(Box::new(ini())),
+        _ => None,
+    }
+}
+
 /// Utility function to create `json` format deserializer.
 #[cfg(feature = "json")]
 pub fn json() -> Json {
@@ -1,9 +1,9 @@
 use crate::{
-    configuration::ConfigurationTree,
+    configuration::{ConfigurationTree, Key, Position, SourceMap},
     error::{ConfigurationError, ErrorCode},
     format::Format,
 };
-use std::default::Default;
+use std::{collections::HashMap, default::Default};
 
 /// Represents `toml` data format.
 pub struct Toml {}
@@ -30,4 +30,205 @@ impl Format for Toml {
     fn describe(&self) -> String {
         "toml".into()
     }
+
+    /// Locates table headers and keys of key/value pairs.
+    ///
+    /// Contents of inline tables and arrays are located at the key they are assigned to.
+    fn source_map(&self, input: &[u8]) -> Option<SourceMap> {
+        let text = std::str::from_utf8(input).ok()?;
+        let mut source_map = SourceMap::new(text);
+
+        let mut table: Vec<Key> = Vec::new();
+        let mut array_tables: HashMap<Vec<Key>, usize> = HashMap::new();
+        // depth of brackets and braces of multi-line values
+        let mut depth: i32 = 0;
+        let mut scanner = Scanner::default();
+
+        for (index, line) in text.lines().enumerate() {
+            if depth > 0 || scanner.in_multiline_string() {
+                depth += bracket_depth(&scanner.unquoted(line));
+                continue;
+            }
+
+            let content = line.trim_start();
+            let position = Position::new(
+                index + 1,
+                line.chars().count() - content.chars().count() + 1,
+            );
+            let unquoted = scanner.unquoted(content);
+            let closing = |bracket: &str| {
+                unquoted
+                    .iter()
+                    .find(|(i, c)| *i >= bracket.len() && *c == ']')
+                    .map_or(content.len(), |(i, _)| *i)
+            };
+
+            if content.starts_with("[[") {
+                let keys = parse_keys(&content[2..closing("[[")]);
+                let count = array_tables.entry(keys.clone()).or_insert(0);
+
+                insert_missing(&mut source_map, &keys, position);
+                table = keys;
+                table.push(Key::Array(*count));
+                source_map.insert(table.clone(), position);
+                *count += 1;
+            } else if content.starts_with('[') {
+                table = parse_keys(&content[1..closing("[")]);
+                insert_missing(&mut source_map, &table, position);
+                source_map.insert(table.clone(), position);
+            } else if let Some(&(separator, _)) = unquoted.iter().find(|(_, c)| *c == '=') {
+                let mut path = table.clone();
+                path.extend(parse_keys(&content[..separator]));
+                insert_missing(&mut source_map, &path, position);
+                source_map.insert(path, position);
+
+                let value: Vec<(usize, char)> = unquoted
+                    .into_iter()
+                    .filter(|(i, _)| *i > separator)
+                    .collect();
+                depth = bracket_depth(&value);
+            }
+        }
+
+        Some(source_map)
+    }
+}
+
+// records positions of ancestors of `path` implicitly defined by it
+fn insert_missing(source_map: &mut SourceMap, path: &[Key], position: Position) {
+    for length in 1..path.len() {
+        if source_map.get(&path[..length]).is_none() {
+            source_map.insert(path[..length].to_vec(), position);
+        }
+    }
+}
+
+const QUOTES: [&str; 4] = ["\"\"\"", "'''", "\"", "'"];
+
+// Finds characters of toml text outside of strings and comments.
+// Multi-line strings might continue on following lines, so the scanner is fed with consecutive lines.
+#[derive(Default)]
+struct Scanner {
+    multiline: Option<&'static str>,
+}
+
+impl Scanner {
+    fn in_multiline_string(&self) -> bool {
+        self.multiline.is_some()
+    }
+
+    // returns byte offsets of unquoted characters along with them
+    fn unquoted(&mut self, line: &str) -> Vec<(usize, char)> {
+        let mut result = Vec::new();
+        let mut quote = self.multiline.take();
+        let mut chars = line.char_indices();
+
+        while let Some((index, c)) = chars.next() {
+            let rest = &line[index..];
+            match quote {
+                Some(q) if rest.starts_with(q) => {
+                    quote = None;
+                    skip(&mut chars, q.len() - 1);
+                }
+                // only basic strings, delimited by double quotes, have escapes
+                Some(q) if c == '\\' && q.starts_with('"') => skip(&mut chars, 1),
+                Some(_) => {}
+                None => match QUOTES.iter().find(|q| rest.starts_with(**q)) {
+                    Some(q) => {
+                        quote = Some(q);
+                        skip(&mut chars, q.len() - 1);
+                    }
+                    None if c == '#' => break,
+                    None => result.push((index, c)),
+                },
+            }
+        }
+
+        self.multiline = quote.filter(|q| q.len() == 3);
+        result
+    }
+}
+
+fn skip(chars: &mut std::str::CharIndices, count: usize) {
+    for _ in 0..count {
+        chars.next();
+    }
+}
+
+fn find_unquoted(text: &str, needle: char) -> Option<usize> {
+    Scanner::default()
+        .unquoted(text)
+        .into_iter()
+        .find(|(_, c)| *c == needle)
+        .map(|(index, _)| index)
+}
+
+fn parse_keys(text: &str) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut rest = text;
+
+    loop {
+        let (key, remainder) = match find_unquoted(rest, '.') {
+            Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+            None => (rest, None),
+        };
+
+        let key = key.trim();
+        let key = match key.strip_prefix('"').and_then(|k| k.strip_suffix('"')) {
+            Some(basic) => unescape(basic),
+            None => key
+                .strip_prefix('\'')
+                .and_then(|k| k.strip_suffix('\''))
+                .unwrap_or(key)
+                .to_owned(),
+        };
+        keys.push(Key::Map(key));
+
+        match remainder {
+            Some(remainder) => rest = remainder,
+            None => return keys,
+        }
+    }
+}
+
+// resolves escape sequences of basic strings, leaving invalid ones intact
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some('b') => Some('\u{8}'),
+            Some('t') => Some('\t'),
+            Some('n') => Some('\n'),
+            Some('f') => Some('\u{c}'),
+            Some('r') => Some('\r'),
+            Some('"') => Some('"'),
+            Some('\\') => Some('\\'),
+            Some(u @ 'u') | Some(u @ 'U') => {
+                let length = if u == 'u' { 4 } else { 8 };
+                let code: String = chars.by_ref().take(length).collect();
+                u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(std::char::from_u32)
+            }
+            _ => None,
+        };
+        result.push(escaped.unwrap_or(std::char::REPLACEMENT_CHARACTER));
+    }
+
+    result
+}
+
+fn bracket_depth(unquoted: &[(usize, char)]) -> i32 {
+    unquoted.iter().fold(0, |depth, (_, c)| match c {
+        '[' | '{' => depth + 1,
+        ']' | '}' => depth - 1,
+        _ => depth,
+    })
 }
@@ -1,9 +1,13 @@
 use crate::{
-    configuration::ConfigurationTree,
+    configuration::{ConfigurationTree, Key, Position, SourceMap},
     error::{ConfigurationError, ErrorCode},
     format::Format,
 };
 use std::default::Default;
+use yaml_rust::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::Marker,
+};
 
 /// Represents `yaml` data format.
 pub struct Yaml {}
@@ -30,4 +34,105 @@ impl Format for Yaml {
     fn describe(&self) -> String {
         "yaml".into()
     }
+
+    fn source_map(&self, input: &[u8]) -> Option<SourceMap> {
+        let text = std::str::from_utf8(input).ok()?;
+
+        let mut locator = Locator {
+            frames: Vec::new(),
+            path: Vec::new(),
+            source_map: SourceMap::new(text),
+        };
+        // positions found before parsing error are still useful
+        let _ = Parser::new(text.chars()).load(&mut locator, false);
+
+        Some(locator.source_map)
+    }
+}
+
+enum Frame {
+    Map { has_key: bool },
+    Sequence { index: usize },
+}
+
+// Follows yaml parser events recording positions of map keys and sequence elements.
+struct Locator {
+    frames: Vec<Frame>,
+    path: Vec<Key>,
+    source_map: SourceMap,
+}
+
+impl Locator {
+    // returns whether node is a key of a map
+    fn begin_node(&mut self, mark: Marker) -> bool {
+        match self.frames.last() {
+            Some(Frame::Map { has_key: false }) => true,
+            Some(Frame::Sequence { index }) => {
+                self.path.push(Key::Array(*index));
+                self.source_map.insert(
+                    self.path.clone(),
+                    Position::new(mark.line(), mark.col() + 1),
+                );
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn end_node(&mut self) {
+        match self.frames.last_mut() {
+            Some(Frame::Map { has_key }) => {
+                if *has_key {
+                    self.path.pop();
+                } else {
+                    // complex keys cannot be represented in configuration paths
+                    self.path.push(Key::Map(String::new()));
+                }
+                *has_key = !*has_key;
+            }
+            Some(Frame::Sequence { index }) => {
+                self.path.pop();
+                *index += 1;
+            }
+            None => {}
+        }
+    }
+}
+
+impl MarkedEventReceiver for Locator {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::Scalar(value, ..) => {
+                if self.begin_node(mark) {
+                    self.path.push(Key::Map(value));
+                    self.source_map.insert(
+                        self.path.clone(),
+                        Position::new(mark.line(), mark.col() + 1),
+                    );
+                    if let Some(Frame::Map { has_key }) = self.frames.last_mut() {
+                        *has_key = true;
+                    }
+                } else {
+                    self.end_node();
+                }
+            }
+            Event::Alias(_) => {
+                self.begin_node(mark);
+                self.end_node();
+            }
+            Event::MappingStart(_) => {
+                self.begin_node(mark);
+                self.frames.push(Frame::Map { has_key: false });
+            }
+            Event::SequenceStart(_) => {
+                self.begin_node(mark);
+                self.frames.push(Frame::Sequence { index: 0 });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.frames.pop();
+                self.end_node();
+            }
+            _ => {}
+        }
+    }
 }
@@ -96,5 +96,6 @@ pub mod format;
 mod parsing;
 /// Configuration providers
 pub mod provider;
+mod ser;
 /// Configuration sources
 pub mod source;
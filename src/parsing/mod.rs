@@ -1,31 +1,350 @@
 use crate::{
-    configuration::{CompoundKey, Key},
+    configuration::{CompoundKey, Key, Query, QuerySegment},
     error::{ConfigurationError, ErrorCode},
 };
 
+/// Parses `input` into [`CompoundKey`].
+///
+/// Keys are separated by `:` and surrounding whitespace is ignored.
+/// Array keys are written as `[index]`, `[-index]` to count from the end of array, with `[-1]` being the last element,
+/// or `[start..end]` to select a slice of array, where both bounds can be negative or omitted. Map keys are written verbatim, with `:`, `[`, `]`, `"` and `\`
+/// escaped by preceding them with `\`, or enclosed in double quotes, in which case only `"` and `\` need escaping.
+/// Quoted keys retain their whitespace and can be empty.
 pub(crate) fn str_to_key(input: &str) -> Result<CompoundKey, ConfigurationError> {
-    let mut result = Vec::new();
+    let segments = KeyParser::new(input, false).parse()?;
 
-    if input.is_empty() {
-        return Ok(result.into());
+    Ok(segments
+        .into_iter()
+        .map(|segment| match segment {
+            QuerySegment::Key(key) => key,
+            _ => unreachable!("wildcards are only parsed in queries"),
+        })
+        .collect::<Vec<Key>>()
+        .into())
+}
+
+/// Parses `input` into [`Query`].
+///
+/// Grammar is the same as in [`str_to_key`], except for keys consisting only of unescaped `*` or `**`
+/// and array keys written as `[*]`, which become wildcards.
+pub(crate) fn str_to_query(input: &str) -> Result<Query, ConfigurationError> {
+    KeyParser::new(input, true).parse().map(Query::new)
+}
+
+// positions reported in errors are counted in characters, starting from 1
+struct KeyParser {
+    chars: Vec<char>,
+    index: usize,
+    wildcards: bool,
+}
+
+struct ArrayBound {
+    negative: bool,
+    digits: String,
+    position: usize,
+}
+
+impl KeyParser {
+    fn new(input: &str, wildcards: bool) -> Self {
+        KeyParser {
+            chars: input.chars().collect(),
+            index: 0,
+            wildcards,
+        }
     }
 
-    // accept string is a format key1:[index1]:key2:key3:[index2]
-    for potential_key in input.split_terminator(':') {
-        let trimmed_key = potential_key.trim();
+    fn parse(mut self) -> Result<Vec<QuerySegment>, ConfigurationError> {
+        let mut result = Vec::new();
+
+        if self.chars.is_empty() {
+            return Ok(result);
+        }
+
+        loop {
+            result.push(self.key()?);
 
-        if trimmed_key.starts_with('[') && trimmed_key.ends_with(']') {
-            let trimmed_key = trimmed_key[1..trimmed_key.len() - 1].trim();
-            match trimmed_key.parse::<usize>() {
-                Ok(i) => result.push(Key::Array(i)),
-                Err(e) => return Err(ErrorCode::ParsingError(format!("Error occured while parsing `{}` : {}", trimmed_key, e.to_string())).into()),
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Ok(result),
+                Some(':') => self.index += 1,
+                Some(c) => {
+                    return Err(self.error(format!(
+                        "Expected `:` but found `{}` at position {}",
+                        c,
+                        self.position()
+                    )))
+                }
             }
+        }
+    }
+
+    fn key(&mut self) -> Result<QuerySegment, ConfigurationError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => self.array_key(),
+            Some('"') => self.quoted_key(),
+            _ => self.bare_key(),
+        }
+    }
+
+    fn array_key(&mut self) -> Result<QuerySegment, ConfigurationError> {
+        let start = self.position();
+        self.index += 1;
+        self.skip_whitespace();
+
+        let wildcard = self.wildcards && self.peek() == Some('*');
+        let (first, range) = if wildcard {
+            self.index += 1;
+            (None, None)
         } else {
-            result.push(Key::Map(trimmed_key.to_owned()))
+            let first = self.array_bound();
+            self.skip_whitespace();
+
+            let range = if self.peek() == Some('.') && self.chars.get(self.index + 1) == Some(&'.')
+            {
+                self.index += 2;
+                self.skip_whitespace();
+                Some(self.array_bound())
+            } else {
+                None
+            };
+
+            (first, range)
+        };
+
+        self.skip_whitespace();
+        match self.peek() {
+            Some(']') => self.index += 1,
+            Some(c) => {
+                return Err(self.error(format!(
+                "Unexpected character `{}` at position {} in array index starting at position {}",
+                c,
+                self.position(),
+                start
+            )))
+            }
+            None => {
+                return Err(self.error(format!(
+                    "Unterminated array index starting at position {}",
+                    start
+                )))
+            }
+        }
+
+        if wildcard {
+            return Ok(QuerySegment::AnyIndex);
         }
+
+        let key = match (first, range) {
+            (Some(bound), None) if bound.negative => match self.parse_bound::<usize>(&bound)? {
+                0 => {
+                    return Err(self.error(format!(
+                        "Invalid array index `-0` at position {}, indices counting from the end start at `-1`",
+                        bound.position
+                    )))
+                }
+                n => Key::ArrayFromEnd(n),
+            },
+            (Some(bound), None) => Key::Array(self.parse_bound(&bound)?),
+            (None, None) => {
+                return Err(self.error(format!("Missing array index at position {}", start + 1)))
+            }
+            (start, Some(end)) => {
+                let start = match start {
+                    Some(bound) => self.parse_signed_bound(&bound)?,
+                    None => 0,
+                };
+                let end = match end {
+                    Some(bound) => Some(self.parse_signed_bound(&bound)?),
+                    None => None,
+                };
+                Key::Slice(start, end)
+            }
+        };
+
+        Ok(QuerySegment::Key(key))
     }
 
-    Ok(result.into())
+    // optionally signed number, `None` if there are no digits
+    fn array_bound(&mut self) -> Option<ArrayBound> {
+        let index = self.index;
+        let position = self.position();
+        let negative = self.peek() == Some('-');
+        if negative {
+            self.index += 1;
+        }
+
+        let mut digits = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit()) {
+            digits.push(c);
+            self.index += 1;
+        }
+
+        if digits.is_empty() {
+            // lone `-` is reported as unexpected character
+            self.index = index;
+            return None;
+        }
+
+        Some(ArrayBound {
+            negative,
+            digits,
+            position,
+        })
+    }
+
+    fn parse_bound<T>(&self, bound: &ArrayBound) -> Result<T, ConfigurationError>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        bound.digits.parse::<T>().map_err(|e| {
+            self.error(format!(
+                "Error occured while parsing `{}` at position {} : {}",
+                bound.digits, bound.position, e
+            ))
+        })
+    }
+
+    fn parse_signed_bound(&self, bound: &ArrayBound) -> Result<isize, ConfigurationError> {
+        let value = self.parse_bound::<isize>(bound)?;
+        Ok(if bound.negative { -value } else { value })
+    }
+
+    fn quoted_key(&mut self) -> Result<QuerySegment, ConfigurationError> {
+        let start = self.position();
+        self.index += 1;
+
+        let mut key = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(QuerySegment::Key(Key::Map(key))),
+                Some('\\') => match self.next() {
+                    Some(c) => key.push(c),
+                    None => break,
+                },
+                Some(c) => key.push(c),
+                None => break,
+            }
+        }
+
+        Err(self.error(format!(
+            "Unterminated quoted key starting at position {}",
+            start
+        )))
+    }
+
+    fn bare_key(&mut self) -> Result<QuerySegment, ConfigurationError> {
+        let start = self.position();
+
+        if self.wildcards && self.peek() == Some('*') {
+            return self.wildcard();
+        }
+
+        let mut key = String::new();
+        // whitespace is only part of the key if something follows it
+        let mut whitespace = String::new();
+
+        while let Some(c) = self.peek() {
+            match c {
+                ':' => break,
+                '[' | ']' | '"' => {
+                    return Err(self.error(format!(
+                        "Unexpected character `{}` at position {}, it has to be escaped or the key quoted",
+                        c,
+                        self.position()
+                    )))
+                }
+                '\\' => {
+                    self.index += 1;
+                    match self.next() {
+                        Some(escaped) => {
+                            key.push_str(&whitespace);
+                            whitespace.clear();
+                            key.push(escaped);
+                        }
+                        None => {
+                            return Err(self.error(format!(
+                                "Missing escaped character at position {}",
+                                self.position()
+                            )))
+                        }
+                    }
+                }
+                c if c.is_whitespace() => {
+                    self.index += 1;
+                    whitespace.push(c);
+                }
+                '*' if self.wildcards => {
+                    return Err(self.error(format!(
+                        "Unexpected `*` at position {}, wildcards have to form whole keys and other `*` have to be escaped",
+                        self.position()
+                    )))
+                }
+                c => {
+                    self.index += 1;
+                    key.push_str(&whitespace);
+                    whitespace.clear();
+                    key.push(c);
+                }
+            }
+        }
+
+        if key.is_empty() {
+            return Err(self.error(format!(
+                "Missing key at position {}, empty keys have to be quoted",
+                start
+            )));
+        }
+
+        Ok(QuerySegment::Key(Key::Map(key)))
+    }
+
+    fn wildcard(&mut self) -> Result<QuerySegment, ConfigurationError> {
+        let start = self.position();
+
+        let mut length = 0;
+        while self.peek() == Some('*') {
+            self.index += 1;
+            length += 1;
+        }
+
+        self.skip_whitespace();
+        match (length, self.peek()) {
+            (1, None) | (1, Some(':')) => Ok(QuerySegment::AnyKey),
+            (2, None) | (2, Some(':')) => Ok(QuerySegment::AnyDescendants),
+            _ => Err(self.error(format!(
+                "Invalid wildcard at position {}, wildcards have to form whole keys and other `*` have to be escaped",
+                start
+            ))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map_or(false, |c| c.is_whitespace()) {
+            self.index += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.index += 1;
+        }
+        c
+    }
+
+    fn position(&self) -> usize {
+        self.index + 1
+    }
+
+    fn error(&self, message: String) -> ConfigurationError {
+        ErrorCode::ParsingError(message).into()
+    }
 }
 
 #[cfg(test)]
@@ -42,19 +361,103 @@ mod tests {
         case("key:[1]", vec![Key::Map("key".into()), Key::Array(1)]),
         case("key:[1]:key2", vec![Key::Map("key".into()), Key::Array(1), Key::Map("key2".into())]),
         case("[1]:key:[2]", vec![Key::Array(1), Key::Map("key".into()), Key::Array(2)]),
-        // strange cases
-        case("1]", vec![Key::Map("1]".into())]),
-        case("1]:[2", vec![Key::Map("1]".into()), Key::Map("[2".into())]),
+        case(" key : [ 1 ] ", vec![Key::Map("key".into()), Key::Array(1)]),
+        // indexing from the end and slicing
+        case("[-1]", vec![Key::ArrayFromEnd(1)]),
+        case("key:[ -12 ]", vec![Key::Map("key".into()), Key::ArrayFromEnd(12)]),
+        case("[1..3]", vec![Key::Slice(1, Some(3))]),
+        case("[ -2 .. -1 ]", vec![Key::Slice(-2, Some(-1))]),
+        case("[..2]", vec![Key::Slice(0, Some(2))]),
+        case("[1..]", vec![Key::Slice(1, None)]),
+        case("[..]", vec![Key::Slice(0, None)]),
+        case("two words", vec![Key::Map("two words".into())]),
+        // quoting and escaping
+        case(r#""http://host:80""#, vec![Key::Map("http://host:80".into())]),
+        case(r#"hosts:"::1":port"#, vec![Key::Map("hosts".into()), Key::Map("::1".into()), Key::Map("port".into())]),
+        case(r#"" padded ""#, vec![Key::Map(" padded ".into())]),
+        case(r#""""#, vec![Key::Map("".into())]),
+        case(r#""say \"hi\" \\ bye""#, vec![Key::Map(r#"say "hi" \ bye"#.into())]),
+        case(r"C\:\\dir", vec![Key::Map(r"C:\dir".into())]),
+        case(r"\[1\]", vec![Key::Map("[1]".into())]),
+        case(r"a\ :b", vec![Key::Map("a ".into()), Key::Map("b".into())]),
     )]
     fn test_key_to_str_success(input: &str, exp: Vec<Key>) {
         let parsed = str_to_key(input).unwrap();
         assert!(exp.iter().eq(parsed.iter()))
     }
 
+    #[rstest(
+        input,
+        message,
+        case("[A]", "`A` at position 2"),
+        case("[]", "Missing array index at position 2"),
+        case("key:[1", "Unterminated array index starting at position 5"),
+        case("[99999999999999999999999]", "at position 2"),
+        case("[-0]", "Invalid array index `-0` at position 2"),
+        case("[-]", "`-` at position 2"),
+        case("[1.3]", "`.` at position 3"),
+        case("[1..3..4]", "`.` at position 6"),
+        case("[99999999999999999999999..]", "at position 2"),
+        case("1]", "`]` at position 2"),
+        case("1]:[2", "`]` at position 2"),
+        case("a:b[0]", "`[` at position 4"),
+        case("a::b", "Missing key at position 3"),
+        case("a:", "Missing key at position 3"),
+        case(r#""open"#, "Unterminated quoted key starting at position 1"),
+        case(r#""a"b"#, "Expected `:` but found `b` at position 4"),
+        case("[1] x", "Expected `:` but found `x` at position 5"),
+        case(r"a\", "Missing escaped character at position 3")
+    )]
+    fn test_key_to_str_failure(input: &str, message: &str) {
+        let error = str_to_key(input).unwrap_err();
+        assert!(std::matches!(error.get_code(), ErrorCode::ParsingError(..)));
+        assert!(
+            error.to_string().contains(message),
+            "`{}` not found in `{}`",
+            message,
+            error
+        );
+    }
+
+    #[rstest(input, exp,
+        case("*", vec![QuerySegment::AnyKey]),
+        case("[*]", vec![QuerySegment::AnyIndex]),
+        case("[ * ]", vec![QuerySegment::AnyIndex]),
+        case("a : ** : b", vec![QuerySegment::Key(Key::Map("a".into())), QuerySegment::AnyDescendants, QuerySegment::Key(Key::Map("b".into()))]),
+        case("routes:[*]:[0]", vec![QuerySegment::Key(Key::Map("routes".into())), QuerySegment::AnyIndex, QuerySegment::Key(Key::Array(0))]),
+        case(r#""*""#, vec![QuerySegment::Key(Key::Map("*".into()))]),
+        case(r"\*\*", vec![QuerySegment::Key(Key::Map("**".into()))]),
+    )]
+    fn test_str_to_query_success(input: &str, exp: Vec<QuerySegment>) {
+        let parsed = str_to_query(input).unwrap();
+        assert_eq!(Query::new(exp), parsed);
+    }
+
+    #[rstest(
+        input,
+        message,
+        case("***", "Invalid wildcard at position 1"),
+        case("a:*b", "Invalid wildcard at position 3"),
+        case("a*", "Unexpected `*` at position 2"),
+        case("[*1]", "`1` at position 3")
+    )]
+    fn test_str_to_query_failure(input: &str, message: &str) {
+        let error = str_to_query(input).unwrap_err();
+        assert!(
+            error.to_string().contains(message),
+            "`{}` not found in `{}`",
+            message,
+            error
+        );
+    }
+
     #[test]
-    fn test_key_to_str_failure() {
-        let input = "[A]";
-        let parsed = str_to_key(input);
-        assert!(parsed.is_err());
+    fn test_str_to_key_treats_wildcards_literally() {
+        let parsed = str_to_key("*:**:[0]").unwrap();
+        assert_eq!(
+            vec![Key::Map("*".into()), Key::Map("**".into()), Key::Array(0)],
+            parsed.to_vec()
+        );
+        assert!(str_to_key("[*]").is_err());
     }
 }
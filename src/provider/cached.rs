@@ -0,0 +1,178 @@
+use crate::{
+    configuration::{Configuration, ConfigurationInfo},
+    error::ConfigurationError,
+    format::{self, Format},
+    provider::{AsyncProvider, Provider},
+    source::{AsyncSource, Source},
+};
+use async_trait::async_trait;
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Keeps last known good content of a source in a local file and serves it when source fails.
+///
+/// Every successful collection writes raw content of the source to cache file.
+/// When source fails, content of cache file is used instead and configuration is marked as stale
+/// in its [`ConfigurationInfo`](crate::configuration::ConfigurationInfo), along with age of the cache.
+/// If there is no cache file, original error is returned.
+/// If cache file exists, but can not be read or deserialized, that error is returned instead,
+/// with context describing failure of the source.
+///
+/// Content is cached only if it was successfully transformed by format, so malformed data never replaces good one.
+/// Failures to write cache file are ignored, as they should not prevent application from starting.
+/// Cache file is accessed with blocking I/O.
+///
+///# Example
+///```rust,no_run
+///use miau::{builder::AsyncConfigurationBuilder, format, provider::Cached, source::AsyncSource};
+///
+///async fn build(source: impl AsyncSource + Send + Sync) {
+///    let mut builder = AsyncConfigurationBuilder::default();
+///    builder.add_provider_async(Cached::new(
+///        source,
+///        format::json(),
+///        "/var/cache/app/remote.json",
+///    ));
+///}
+///```
+pub struct Cached<S, T> {
+    source: S,
+    format: T,
+    path: PathBuf,
+}
+
+impl<S, T: Format> Cached<S, T> {
+    /// Creates new `Cached` provider storing last known good content of `source` in file at `path`.
+    pub fn new<P: AsRef<Path>>(source: S, format: T, path: P) -> Self {
+        Cached {
+            source,
+            format,
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns path to the cache file.
+    pub fn cache_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns time elapsed since cache file was last written, if it exists.
+    pub fn cache_age(&self) -> Option<Duration> {
+        let modified = fs::metadata(&self.path).ok()?.modified().ok()?;
+        Some(
+            SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default(),
+        )
+    }
+
+    fn process(
+        &self,
+        content: Result<Vec<u8>, ConfigurationError>,
+        info: ConfigurationInfo,
+        source_map: bool,
+    ) -> Result<Configuration, ConfigurationError> {
+        match content {
+            Ok(content) => {
+                let definition =
+                    format::transform_definition(&self.format, content.clone(), info, source_map)?;
+                self.store(&content);
+                Ok(definition.into())
+            }
+            Err(error) => match self.restore(info, source_map) {
+                Ok(Some(configuration)) => Ok(configuration),
+                Ok(None) => Err(error.enrich_with_context(format!(
+                    "No cached content available at : {}",
+                    self.path.display()
+                ))),
+                Err(cache_error) => Err(cache_error.enrich_with_context(format!(
+                    "Failed to restore cached content after source failed with : {}",
+                    error
+                ))),
+            },
+        }
+    }
+
+    fn store(&self, content: &[u8]) {
+        // write and rename, so that partially written cache is never read
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+
+        if fs::write(&temporary, content).is_ok() {
+            let _ = fs::rename(&temporary, &self.path);
+        }
+    }
+
+    // missing cache file is not an error, it just means there is nothing to restore
+    fn restore(
+        &self,
+        info: ConfigurationInfo,
+        source_map: bool,
+    ) -> Result<Option<Configuration>, ConfigurationError> {
+        let age = self.cache_age().unwrap_or_default();
+        let content = match fs::read(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                let error: ConfigurationError = e.into();
+                return Err(error.enrich_with_context(format!(
+                    "Failed to read cached content : {}",
+                    self.path.display()
+                )));
+            }
+        };
+        let definition =
+            format::transform_definition(&self.format, content, info.mark_stale(age), source_map)
+                .map_err(|e| {
+                e.enrich_with_context(format!(
+                    "Failed to deserialize cached content : {}",
+                    self.path.display()
+                ))
+            })?;
+
+        Ok(Some(definition.into()))
+    }
+}
+
+impl<S: Source, T: Format> Provider for Cached<S, T> {
+    fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        self.process(self.source.collect(), Provider::describe(self), false)
+    }
+
+    fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        self.process(self.source.collect(), Provider::describe(self), true)
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        ConfigurationInfo::new(self.source.describe(), self.format.describe())
+    }
+}
+
+#[async_trait]
+impl<S, T> AsyncProvider for Cached<S, T>
+where
+    S: AsyncSource + Send + Sync,
+    T: Format + Send + Sync,
+{
+    async fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        let content = self.source.collect().await;
+        self.process(content, AsyncProvider::describe(self), false)
+    }
+
+    async fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        let content = self.source.collect().await;
+        self.process(content, AsyncProvider::describe(self), true)
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        ConfigurationInfo::new(self.source.describe(), self.format.describe())
+    }
+}
@@ -17,7 +17,7 @@ impl Provider for Configuration {
             .collect();
         ConfigurationInfo::new(
             "other configuration".into(),
-            format!("multiple({})", (&formats).join(",")),
+            format!("multiple({})", formats.join(",")),
         )
     }
 }
@@ -0,0 +1,319 @@
+use super::Provider;
+use crate::{
+    configuration::{Configuration, ConfigurationDefinition, ConfigurationInfo},
+    error::{ConfigurationError, ErrorCode},
+    format::{self, Format},
+    source::{FileSource, Source},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Provides all files from a directory as separate configuration layers.
+///
+/// It is meant to support `conf.d` style drop-in directories.
+/// Files whose names match the glob pattern are sorted lexicographically by their path relative to the directory
+/// and each of them becomes a separate layer with its own [`ConfigurationInfo`](crate::configuration::ConfigurationInfo).
+/// Format of every file is detected from its extension, files whose format can not be detected are skipped.
+///
+/// Pattern supports `*` (any sequence of characters) and `?` (any single character) wildcards
+/// and is matched against file name only.
+///
+/// Symbolic links to directories are followed, but every directory is read at most once,
+/// so links pointing at one of their parents do not cause infinite recursion.
+///
+///# Example
+///```rust
+///use miau::{builder::ConfigurationBuilder, provider::DirectoryProvider};
+///
+///let mut builder = ConfigurationBuilder::default();
+///builder.add_provider(
+///    DirectoryProvider::new("/etc/app/conf.d")
+///        .pattern("*.yaml")
+///        .allow_missing(true),
+///);
+///```
+pub struct DirectoryProvider {
+    path: PathBuf,
+    pattern: String,
+    recursive: bool,
+    include_hidden: bool,
+    allow_missing: bool,
+    allow_empty: bool,
+    formats: HashMap<String, Box<dyn Format + Send + Sync>>,
+}
+
+impl DirectoryProvider {
+    /// Creates new `DirectoryProvider` reading files from directory at `path`.
+    ///
+    /// By default all non-hidden files directly inside the directory are read,
+    /// missing directory is an error and empty directory is not.
+    pub fn new<T: AsRef<Path>>(path: T) -> Self {
+        DirectoryProvider {
+            path: path.as_ref().to_path_buf(),
+            pattern: "*".into(),
+            recursive: false,
+            include_hidden: false,
+            allow_missing: false,
+            allow_empty: true,
+            formats: HashMap::new(),
+        }
+    }
+
+    /// Sets glob pattern that file names have to match to be read.
+    pub fn pattern<T: Into<String>>(mut self, pattern: T) -> Self {
+        self.pattern = pattern.into();
+        self
+    }
+
+    /// Sets whether subdirectories should be read as well.
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Sets whether hidden files and directories (starting with `.`) should be read.
+    pub fn include_hidden(mut self, include_hidden: bool) -> Self {
+        self.include_hidden = include_hidden;
+        self
+    }
+
+    /// Sets whether missing directory should result in empty configuration instead of an error.
+    pub fn allow_missing(mut self, allow_missing: bool) -> Self {
+        self.allow_missing = allow_missing;
+        self
+    }
+
+    /// Sets whether directory without matching files should result in empty configuration instead of an error.
+    pub fn allow_empty(mut self, allow_empty: bool) -> Self {
+        self.allow_empty = allow_empty;
+        self
+    }
+
+    /// Registers `format` used for files with given `extension`.
+    ///
+    /// Formats registered this way take precedence over formats detected by [`from_extension`](crate::format::from_extension).
+    pub fn with_format<T, F>(mut self, extension: T, format: F) -> Self
+    where
+        T: AsRef<str>,
+        F: Format + Send + Sync + 'static,
+    {
+        self.formats
+            .insert(extension.as_ref().to_lowercase(), Box::new(format));
+        self
+    }
+
+    fn find_files(&self) -> Result<Vec<PathBuf>, ConfigurationError> {
+        let mut files = Vec::new();
+        self.visit(&self.path, &mut files, &mut HashSet::new())?;
+        files.sort();
+        Ok(files)
+    }
+
+    fn visit(
+        &self,
+        directory: &Path,
+        files: &mut Vec<PathBuf>,
+        visited: &mut HashSet<PathBuf>,
+    ) -> Result<(), ConfigurationError> {
+        // symbolic links can lead back to directories that were already read
+        let canonical = fs::canonicalize(directory).unwrap_or_else(|_| directory.to_path_buf());
+        if !visited.insert(canonical) {
+            return Ok(());
+        }
+
+        let entries = fs::read_dir(directory)
+            .map_err(|e| -> ConfigurationError { e.into() })
+            .map_err(|e| {
+                e.enrich_with_context(format!(
+                    "Failed to read directory : {}",
+                    directory.display()
+                ))
+            })?;
+
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| -> ConfigurationError { e.into() })
+                .map_err(|e| {
+                    e.enrich_with_context(format!(
+                        "Failed to read directory : {}",
+                        directory.display()
+                    ))
+                })?;
+
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+
+            if !self.include_hidden && name.starts_with('.') {
+                continue;
+            }
+
+            if path.is_dir() {
+                if self.recursive {
+                    self.visit(&path, files, visited)?;
+                }
+            } else if glob_matches(&self.pattern, &name) && self.knows_format(&path) {
+                // relative paths are sorted, so nested files are ordered by their directory first
+                let relative = path.strip_prefix(&self.path).unwrap_or(&path).to_path_buf();
+                files.push(relative);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn knows_format(&self, path: &Path) -> bool {
+        let extension = extension(path);
+        self.formats.contains_key(&extension) || format::from_extension(&extension).is_some()
+    }
+
+    fn collect_file(
+        &self,
+        relative: &Path,
+        source_map: bool,
+    ) -> Result<ConfigurationDefinition, ConfigurationError> {
+        let path = self.path.join(relative);
+
+        match self.formats.get(&extension(&path)) {
+            Some(format) => collect_file(&path, format.as_ref(), source_map),
+            None => collect_file(&path, detect_format(&path)?.as_ref(), source_map),
+        }
+    }
+
+    fn collect_files(&self, source_maps: bool) -> Result<Configuration, ConfigurationError> {
+        if self.allow_missing && !self.path.exists() {
+            return Ok(Configuration::new_empty());
+        }
+
+        let files = self.find_files()?;
+
+        if files.is_empty() && !self.allow_empty {
+            let error: ConfigurationError = ErrorCode::EmptyConfiguration.into();
+            return Err(error.enrich_with_context(format!(
+                "No files matching `{}` found in directory : {}",
+                self.pattern,
+                self.path.display()
+            )));
+        }
+
+        let mut roots = Vec::with_capacity(files.len());
+        for file in files.iter() {
+            roots.push(self.collect_file(file, source_maps)?);
+        }
+
+        Ok(Configuration {
+            roots,
+            ..Configuration::default()
+        })
+    }
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Detects format of file at `path` based on its extension.
+pub(crate) fn detect_format(
+    path: &Path,
+) -> Result<Box<dyn Format + Send + Sync>, ConfigurationError> {
+    let extension = extension(path);
+    format::from_extension(&extension).ok_or_else(|| {
+        let error: ConfigurationError = ErrorCode::DeserializationError(format!(
+            "unable to detect format of file with extension `{}`",
+            extension
+        ))
+        .into();
+        error.enrich_with_context(format!("Failed to read file : {}", path.display()))
+    })
+}
+
+/// Reads file at `path` as a single configuration layer.
+pub(crate) fn collect_file(
+    path: &Path,
+    format: &dyn Format,
+    source_map: bool,
+) -> Result<ConfigurationDefinition, ConfigurationError> {
+    let source = FileSource::from_path(path);
+    let info = ConfigurationInfo::new(source.describe(), format.describe());
+
+    format::transform_definition(format, source.collect()?, info, source_map).map_err(|e| {
+        e.enrich_with_context(format!("Failed to deserialize file : {}", path.display()))
+    })
+}
+
+impl Provider for DirectoryProvider {
+    fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        self.collect_files(false)
+    }
+
+    fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        self.collect_files(true)
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        ConfigurationInfo::new(self.path.display().to_string(), "directory".into())
+    }
+}
+
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                n += 1;
+            }
+            Some(c) if *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((bp, bn)) => {
+                    backtrack = Some((bp, bn + 1));
+                    p = bp + 1;
+                    n = bn + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        pattern,
+        name,
+        exp,
+        case("*", "config.yaml", true),
+        case("*.yaml", "config.yaml", true),
+        case("*.yaml", "config.yml", false),
+        case("??-*.json", "10-base.json", true),
+        case("??-*.json", "100-base.json", false),
+        case("a*b*c", "aXXbYYc", true),
+        case("a*b*c", "aXXbYY", false),
+        case("config.json", "config.json", true),
+        case("", "config.json", false)
+    )]
+    fn test_glob_matches(pattern: &str, name: &str, exp: bool) {
+        assert_eq!(exp, glob_matches(pattern, name));
+    }
+}
@@ -0,0 +1,194 @@
+use super::{
+    directory::{collect_file, detect_format},
+    Provider,
+};
+use crate::{
+    configuration::{Configuration, ConfigurationInfo},
+    error::ConfigurationError,
+};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+/// Discovers configuration files in standard locations.
+///
+/// For application named `app` and each of base file names, following locations are searched, from the lowest to the highest priority:
+/// * `/etc/app/`
+/// * `$XDG_CONFIG_HOME/app/`
+/// * `~/.config/app/`
+/// * the nearest directory containing file with given name, walking up from current directory
+///
+/// Every existing file becomes a separate configuration layer, ordered as above.
+/// Format of every file is detected from its extension.
+/// All searched paths, including ones that do not exist, can be retrieved for diagnostics with [`searched_paths`](Self::searched_paths).
+///
+///# Example
+///```rust
+///use miau::{builder::ConfigurationBuilder, provider::DiscoveryProvider};
+///
+///let discovery = DiscoveryProvider::new("app", &["app.toml"]);
+///
+///for path in discovery.searched_paths() {
+///     println!("{}", path.display());
+///}
+///
+///let mut builder = ConfigurationBuilder::default();
+///builder.add_provider(discovery);
+///```
+pub struct DiscoveryProvider {
+    application: String,
+    file_names: Vec<String>,
+    system_dir: Option<PathBuf>,
+    xdg_config_dir: Option<PathBuf>,
+    home_config_dir: Option<PathBuf>,
+    start_dir: Option<PathBuf>,
+}
+
+impl DiscoveryProvider {
+    /// Creates new `DiscoveryProvider` for `application` looking for files named `file_names`.
+    ///
+    /// Environment variables and current directory are read at the moment of creation.
+    pub fn new<T: Into<String>, N: AsRef<str>>(application: T, file_names: &[N]) -> Self {
+        let home = env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(PathBuf::from);
+
+        DiscoveryProvider {
+            application: application.into(),
+            file_names: file_names.iter().map(|n| n.as_ref().to_owned()).collect(),
+            system_dir: Some(PathBuf::from("/etc")),
+            xdg_config_dir: env::var_os("XDG_CONFIG_HOME").map(PathBuf::from),
+            home_config_dir: home.map(|h| h.join(".config")),
+            start_dir: env::current_dir().ok(),
+        }
+    }
+
+    /// Sets system wide configuration directory, `/etc` by default.
+    ///
+    /// Passing `None` disables searching it.
+    pub fn system_dir<T: AsRef<Path>>(mut self, dir: Option<T>) -> Self {
+        self.system_dir = dir.map(|d| d.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets directory from which upward search starts, current directory by default.
+    ///
+    /// Passing `None` disables upward search.
+    pub fn start_dir<T: AsRef<Path>>(mut self, dir: Option<T>) -> Self {
+        self.start_dir = dir.map(|d| d.as_ref().to_path_buf());
+        self
+    }
+
+    fn application_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+
+        let roots = [
+            &self.system_dir,
+            &self.xdg_config_dir,
+            &self.home_config_dir,
+        ];
+
+        for root in roots.iter().filter_map(|r| r.as_ref()) {
+            let dir = root.join(&self.application);
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+
+        dirs
+    }
+
+    fn upward_dirs(&self) -> Vec<&Path> {
+        match self.start_dir {
+            Some(ref start) => start.ancestors().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns all paths that are searched.
+    ///
+    /// Paths in standard locations are listed from the lowest priority.
+    /// Paths of upward search follow, starting from the nearest directory.
+    pub fn searched_paths(&self) -> Vec<PathBuf> {
+        let mut result = Vec::new();
+
+        for dir in self.application_dirs() {
+            for name in self.file_names.iter() {
+                result.push(dir.join(name));
+            }
+        }
+
+        for dir in self.upward_dirs() {
+            for name in self.file_names.iter() {
+                result.push(dir.join(name));
+            }
+        }
+
+        result
+    }
+
+    /// Returns paths of files that exist and will become configuration layers, in order of priority.
+    pub fn found_paths(&self) -> Vec<PathBuf> {
+        let mut result: Vec<PathBuf> = Vec::new();
+
+        for dir in self.application_dirs() {
+            for name in self.file_names.iter() {
+                let path = dir.join(name);
+                if path.is_file() {
+                    result.push(path);
+                }
+            }
+        }
+
+        let upward_dirs = self.upward_dirs();
+        for name in self.file_names.iter() {
+            let nearest = upward_dirs
+                .iter()
+                .map(|dir| dir.join(name))
+                .find(|path| path.is_file());
+
+            if let Some(path) = nearest {
+                if !result.contains(&path) {
+                    result.push(path);
+                }
+            }
+        }
+
+        result
+    }
+
+    fn collect_files(&self, source_maps: bool) -> Result<Configuration, ConfigurationError> {
+        let mut roots = Vec::new();
+
+        for path in self.found_paths() {
+            roots.push(collect_file(
+                &path,
+                detect_format(&path)?.as_ref(),
+                source_maps,
+            )?);
+        }
+
+        Ok(Configuration {
+            roots,
+            ..Configuration::default()
+        })
+    }
+}
+
+impl Provider for DiscoveryProvider {
+    fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        self.collect_files(false)
+    }
+
+    fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        self.collect_files(true)
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        ConfigurationInfo::new(
+            format!("discovery({})", self.application),
+            "multiple".into(),
+        )
+    }
+}
@@ -1,81 +1,147 @@
 use super::Provider;
 use crate::{
-    configuration::{merge, Configuration, ConfigurationInfo, ConfigurationTree, Key, Value},
+    configuration::{Configuration, ConfigurationInfo, ConfigurationTree, Key, Value},
     parsing,
 };
 use std::{collections::HashMap, convert::Into, default::Default, env};
 
 /// Provides environmental variables as configuration.
+///
+/// By default names of variables are parsed just like keys, so `db:host` becomes key `host` nested in map `db`.
+/// As most shells do not accept `:` in names, a different [`separator`](Self::separator) can be set.
+/// Combined with [`KeyMode`](crate::configuration::KeyMode) ignoring case, it lets `APP_DB_HOST` override `db:host`:
+///
+///```rust
+///use miau::{builder::ConfigurationBuilder, configuration::{ConfigurationRead, KeyMode}, format, provider::EnvironmentProvider, source::InMemorySource};
+///
+///std::env::set_var("APP_DB_HOST", "db.internal");
+///
+///let mut builder = ConfigurationBuilder::default();
+///builder.add(InMemorySource::from_string_slice(r#"{ "db" : { "host" : "localhost" } }"#), format::json());
+///builder.add_provider(EnvironmentProvider::with_prefix("APP_").strip_prefix(true).separator("_"));
+///builder.key_mode(KeyMode::IgnoreCase);
+///
+///let configuration = builder.build().unwrap();
+///assert_eq!(Some("db.internal".to_string()), configuration.get("db:host"));
+///```
 pub struct EnvironmentProvider {
     prefix: Option<String>,
+    strip_prefix: bool,
+    separator: Option<String>,
 }
 
 impl EnvironmentProvider {
     /// Creates new `EnvironmentProvider` that retrives all environmental variables.
     pub fn new() -> Self {
-        EnvironmentProvider { prefix: None }
+        EnvironmentProvider {
+            prefix: None,
+            strip_prefix: false,
+            separator: None,
+        }
     }
 
     /// Creates new `EnvironmentProvider` that retrives environmental variables prefixed with `prefix`.
     pub fn with_prefix<T: Into<String>>(prefix: T) -> Self {
         EnvironmentProvider {
             prefix: Some(prefix.into()),
+            ..EnvironmentProvider::new()
         }
     }
 
-    fn get(&self) -> Configuration {
-        let vars = env::vars();
-
-        let node = match self.prefix {
-            Some(ref prefix) => push(vars.filter(|(key, _)| key.starts_with(prefix))),
-            None => push(vars),
-        };
+    /// Sets whether prefix is removed from names of variables before they become keys.
+    pub fn strip_prefix(mut self, strip_prefix: bool) -> Self {
+        self.strip_prefix = strip_prefix;
+        self
+    }
 
-        match node {
-            Some(node) => Configuration::new_singular(
-                ConfigurationInfo::new("environment", "environment"),
-                node,
-            ),
-            None => Configuration::new_empty(),
-        }
+    /// Sets separator of nested keys in names of variables, e.g. `_` or `__`.
+    ///
+    /// Names are split on every occurrence of separator, without any further parsing.
+    /// Variables whose names contain empty keys, e.g. because of doubled separator, are skipped.
+    /// If a name is a prefix of another one, e.g. `A` and `A_B`, the nested key wins and `A` is skipped.
+    pub fn separator<T: Into<String>>(mut self, separator: T) -> Self {
+        self.separator = Some(separator.into()).filter(|s| !s.is_empty());
+        self
     }
-}
 
-fn push(keys: impl Iterator<Item = (String, String)>) -> Option<ConfigurationTree> {
-    let mut trees: Vec<ConfigurationTree> = Vec::new();
-    for (key, value) in keys {
-        if let Ok(ckey) = parsing::str_to_key(key.as_ref()) {
-            let all_map = ckey.iter().all(|k| std::matches!(k, Key::Map(..)));
+    fn get(&self) -> Configuration {
+        let mut root = HashMap::new();
+
+        // order of variables is unspecified, sorting them makes conflicting ones resolve the same way every time
+        let mut vars: Vec<(String, String)> = env::vars().collect();
+        vars.sort();
 
-            if !all_map {
-                continue;
+        for (name, value) in vars {
+            let name = match self.prefix {
+                Some(ref prefix) if self.strip_prefix => match name.strip_prefix(prefix.as_str()) {
+                    Some(name) => name,
+                    None => continue,
+                },
+                Some(ref prefix) if !name.starts_with(prefix.as_str()) => continue,
+                _ => name.as_str(),
+            };
+
+            if let Some(keys) = self.keys(name) {
+                insert(&mut root, &keys, value);
             }
+        }
 
-            trees.push(create_tree(ckey.iter().map(|k| k.unwrap_map()), value));
+        if root.is_empty() {
+            Configuration::new_empty()
+        } else {
+            Configuration::new_singular(
+                ConfigurationInfo::new("environment", "environment"),
+                ConfigurationTree::Map(root),
+            )
         }
     }
 
-    let mut drain = trees.drain(..);
-    match drain.next() {
-        Some(node) => {
-            if let Ok(final_node) = drain.try_fold(node, merge) {
-                Some(final_node)
-            } else {
-                None
+    fn keys(&self, name: &str) -> Option<Vec<String>> {
+        let keys: Vec<String> = match self.separator {
+            Some(ref separator) => name
+                .split(separator.as_str())
+                .map(|k| k.to_owned())
+                .collect(),
+            None => {
+                let ckey = parsing::str_to_key(name).ok()?;
+                ckey.iter()
+                    .map(|k| match k {
+                        Key::Map(key) => Some(key.clone()),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?
             }
+        };
+
+        if keys.is_empty() || keys.iter().any(|k| k.is_empty()) {
+            None
+        } else {
+            Some(keys)
         }
-        None => None,
     }
 }
 
-fn create_tree(mut keys: impl Iterator<Item = String>, value: String) -> ConfigurationTree {
-    match keys.next() {
-        Some(key) => {
-            let mut map = HashMap::new();
-            map.insert(key, create_tree(keys, value));
-            ConfigurationTree::Map(map)
-        }
-        None => ConfigurationTree::Value(Some(Value::String(value))),
+// of conflicting variables, like `A` and `A_B`, the nested one wins regardless of their order
+fn insert(map: &mut HashMap<String, ConfigurationTree>, keys: &[String], value: String) {
+    let (key, rest) = match keys.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+
+    if rest.is_empty() {
+        map.entry(key.clone())
+            .or_insert(ConfigurationTree::Value(Some(Value::String(value))));
+        return;
+    }
+
+    let child = map
+        .entry(key.clone())
+        .or_insert_with(|| ConfigurationTree::Map(HashMap::new()));
+    if let ConfigurationTree::Value(_) = child {
+        *child = ConfigurationTree::Map(HashMap::new());
+    }
+    if let ConfigurationTree::Map(child) = child {
+        insert(child, rest, value);
     }
 }
 
@@ -0,0 +1,166 @@
+use super::Provider;
+use crate::{
+    configuration::{Configuration, ConfigurationInfo, ConfigurationTree, Value},
+    error::{ConfigurationError, ErrorCode},
+};
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+const CREDENTIALS_DIRECTORY: &str = "CREDENTIALS_DIRECTORY";
+
+/// Provides directory in which every file holds single configuration value.
+///
+/// It is meant to support Kubernetes volume-mounted ConfigMaps and Secrets, Docker secrets (`/run/secrets`)
+/// and systemd credentials (`$CREDENTIALS_DIRECTORY`).
+/// Each file name becomes a key and its contents, with surrounding whitespace trimmed, become a string value.
+/// Files in subdirectories are placed in nested maps named after the subdirectories.
+///
+/// Entries whose names start with `..` are skipped. Kubernetes uses them (e.g. `..data`) to atomically swap
+/// mounted content, while exposing actual keys as symbolic links in the top level directory.
+///
+///# Example
+///```rust
+///use miau::{builder::ConfigurationBuilder, provider::KeyPerFileProvider};
+///
+///let mut builder = ConfigurationBuilder::default();
+///builder.add_provider(KeyPerFileProvider::new("/run/secrets").allow_missing(true));
+///```
+pub struct KeyPerFileProvider {
+    path: Option<PathBuf>,
+    trim: bool,
+    allow_missing: bool,
+}
+
+impl KeyPerFileProvider {
+    /// Creates new `KeyPerFileProvider` reading files from directory at `path`.
+    pub fn new<T: AsRef<Path>>(path: T) -> Self {
+        KeyPerFileProvider {
+            path: Some(path.as_ref().to_path_buf()),
+            trim: true,
+            allow_missing: false,
+        }
+    }
+
+    /// Creates new `KeyPerFileProvider` reading systemd credentials.
+    ///
+    /// Directory is taken from `$CREDENTIALS_DIRECTORY` environment variable at the moment of creation.
+    /// If variable is not set (i.e. application was not started by systemd with credentials), provider yields empty configuration.
+    pub fn credentials() -> Self {
+        KeyPerFileProvider {
+            path: env::var_os(CREDENTIALS_DIRECTORY).map(PathBuf::from),
+            trim: true,
+            allow_missing: false,
+        }
+    }
+
+    /// Sets whether whitespace surrounding file contents should be trimmed.
+    ///
+    /// It is enabled by default.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Sets whether missing directory should result in empty configuration instead of an error.
+    pub fn allow_missing(mut self, allow_missing: bool) -> Self {
+        self.allow_missing = allow_missing;
+        self
+    }
+
+    fn read_directory(
+        &self,
+        directory: &Path,
+    ) -> Result<HashMap<String, ConfigurationTree>, ConfigurationError> {
+        let mut result = HashMap::new();
+
+        let entries = fs::read_dir(directory)
+            .map_err(|e| -> ConfigurationError { e.into() })
+            .map_err(|e| {
+                e.enrich_with_context(format!(
+                    "Failed to read directory : {}",
+                    directory.display()
+                ))
+            })?;
+
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| -> ConfigurationError { e.into() })
+                .map_err(|e| {
+                    e.enrich_with_context(format!(
+                        "Failed to read directory : {}",
+                        directory.display()
+                    ))
+                })?;
+
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with("..") {
+                continue;
+            }
+
+            // follows symbolic links, which is required to read Kubernetes mounts
+            let path = entry.path();
+            let node = if path.is_dir() {
+                ConfigurationTree::Map(self.read_directory(&path)?)
+            } else {
+                ConfigurationTree::Value(Some(Value::String(self.read_file(&path)?)))
+            };
+
+            result.insert(name, node);
+        }
+
+        Ok(result)
+    }
+
+    fn read_file(&self, path: &Path) -> Result<String, ConfigurationError> {
+        let content = fs::read(path)
+            .map_err(|e| -> ConfigurationError { e.into() })
+            .map_err(|e| {
+                e.enrich_with_context(format!("Failed to read file : {}", path.display()))
+            })?;
+
+        let content = String::from_utf8(content)
+            .map_err(|e| -> ConfigurationError {
+                ErrorCode::DeserializationError(e.to_string()).into()
+            })
+            .map_err(|e| {
+                e.enrich_with_context(format!("Failed to read file : {}", path.display()))
+            })?;
+
+        if self.trim {
+            Ok(content.trim().to_owned())
+        } else {
+            Ok(content)
+        }
+    }
+}
+
+impl Provider for KeyPerFileProvider {
+    fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return Ok(Configuration::new_empty()),
+        };
+
+        if self.allow_missing && !path.exists() {
+            return Ok(Configuration::new_empty());
+        }
+
+        let root = self.read_directory(path)?;
+
+        Ok(Configuration::new_singular(
+            self.describe(),
+            ConfigurationTree::Map(root),
+        ))
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        let source = match self.path {
+            Some(ref path) => path.display().to_string(),
+            None => format!("${}", CREDENTIALS_DIRECTORY),
+        };
+        ConfigurationInfo::new(source, "key-per-file".into())
+    }
+}
@@ -1,10 +1,24 @@
+mod cached;
 mod config;
+mod directory;
+mod discovery;
 mod env;
 mod hashmap;
+mod key_per_file;
 mod node;
+mod optional;
+mod resilience;
+mod serialize;
 mod traits;
 
+pub use cached::Cached;
+pub use directory::DirectoryProvider;
+pub use discovery::DiscoveryProvider;
 pub use env::EnvironmentProvider;
+pub use key_per_file::KeyPerFileProvider;
+pub use optional::Optional;
+pub use resilience::{Retry, RetryPolicy, Timeout, Timer};
+pub use serialize::SerializeProvider;
 pub use traits::AsyncProvider;
 pub use traits::Provider;
 pub use traits::ProviderStruct;
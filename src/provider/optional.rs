@@ -0,0 +1,146 @@
+use crate::{
+    configuration::{Configuration, ConfigurationInfo, ConfigurationTree},
+    error::{ConfigurationError, ErrorCode},
+    format::Format,
+    provider::{AsyncProvider, Provider, ProviderStruct},
+    source::{AsyncSource, Source},
+};
+use async_trait::async_trait;
+use std::{collections::HashMap, io::ErrorKind};
+
+/// Makes wrapped source or provider optional.
+///
+/// When wrapped provider fails because its source does not exist (e.g. file is not found)
+/// or source returned no content, layer is skipped instead of failing whole build.
+/// Skipped layer is empty and it is marked as skipped in its [`ConfigurationInfo`](crate::configuration::ConfigurationInfo).
+/// All other errors, like lack of permissions or malformed content, are still propagated.
+///
+/// Wrapped source reports content consisting only of whitespace as [`EmptySource`](crate::error::ErrorCode::EmptySource) error.
+/// Use [`synchronous`](Self::synchronous) or [`asynchronous`](Self::asynchronous) to make source and format pair optional.
+///
+///# Example
+///```rust
+///use miau::{builder::ConfigurationBuilder, format, provider::Optional, source::FileSource};
+///
+///let mut builder = ConfigurationBuilder::default();
+///builder.add_provider(Optional::synchronous(
+///    FileSource::from_path("./a/path/to/config.local.json"),
+///    format::json(),
+///));
+///
+///let configuration = builder.build().unwrap();
+///assert!(configuration.infos().all(|info| info.is_skipped()));
+///```
+pub struct Optional<T> {
+    inner: T,
+}
+
+impl<T> Optional<T> {
+    /// Makes `inner` source or provider optional.
+    pub fn new(inner: T) -> Self {
+        Optional { inner }
+    }
+}
+
+impl<S: Source, T: Format> Optional<ProviderStruct<Optional<S>, T>> {
+    /// Constructs new optional synchronous source provider.
+    pub fn synchronous(source: S, format: T) -> Self {
+        Optional::new(ProviderStruct::synchronous(Optional::new(source), format))
+    }
+}
+
+impl<S, T> Optional<ProviderStruct<Optional<S>, T>>
+where
+    S: AsyncSource + Send + Sync,
+    T: Format + Send + Sync,
+{
+    /// Constructs new optional asynchronous source provider.
+    pub fn asynchronous(source: S, format: T) -> Self {
+        Optional::new(ProviderStruct::asynchronous(Optional::new(source), format))
+    }
+}
+
+fn check_content(content: Vec<u8>) -> Result<Vec<u8>, ConfigurationError> {
+    if content.iter().all(u8::is_ascii_whitespace) {
+        Err(ErrorCode::EmptySource.into())
+    } else {
+        Ok(content)
+    }
+}
+
+fn skip_reason(error: &ConfigurationError) -> Option<&'static str> {
+    match error.get_code() {
+        ErrorCode::IoError(e) if e.kind() == ErrorKind::NotFound => Some("source not found"),
+        ErrorCode::EmptySource => Some("source is empty"),
+        _ => None,
+    }
+}
+
+fn skip_or_propagate(
+    result: Result<Configuration, ConfigurationError>,
+    info: ConfigurationInfo,
+) -> Result<Configuration, ConfigurationError> {
+    match result {
+        Ok(configuration) => Ok(configuration),
+        Err(error) => match skip_reason(&error) {
+            Some(reason) => Ok(Configuration::new_singular(
+                info.mark_skipped(reason),
+                ConfigurationTree::Map(HashMap::new()),
+            )),
+            None => Err(error),
+        },
+    }
+}
+
+impl<S: Source> Source for Optional<S> {
+    fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        self.inner.collect().and_then(check_content)
+    }
+
+    fn describe(&self) -> String {
+        self.inner.describe()
+    }
+}
+
+#[async_trait]
+impl<S: AsyncSource + Send + Sync> AsyncSource for Optional<S> {
+    async fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        self.inner.collect().await.and_then(check_content)
+    }
+
+    fn describe(&self) -> String {
+        self.inner.describe()
+    }
+}
+
+impl<P: Provider> Provider for Optional<P> {
+    fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        skip_or_propagate(self.inner.collect(), self.inner.describe())
+    }
+
+    fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        skip_or_propagate(self.inner.collect_with_source_maps(), self.inner.describe())
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        self.inner.describe()
+    }
+}
+
+#[async_trait]
+impl<P: AsyncProvider> AsyncProvider for Optional<P> {
+    async fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        skip_or_propagate(self.inner.collect().await, self.inner.describe())
+    }
+
+    async fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        skip_or_propagate(
+            self.inner.collect_with_source_maps().await,
+            self.inner.describe(),
+        )
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        self.inner.describe()
+    }
+}
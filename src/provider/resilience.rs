@@ -0,0 +1,346 @@
+use crate::{
+    configuration::{Configuration, ConfigurationInfo},
+    error::{ConfigurationError, ErrorCode},
+    provider::AsyncProvider,
+    source::AsyncSource,
+};
+use async_trait::async_trait;
+use futures::{
+    future::{self, Either},
+    pin_mut,
+};
+use std::{
+    future::Future,
+    io::{self, ErrorKind},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Abstracts away timers of asynchronous runtimes.
+///
+/// `miau` does not depend on any runtime, so timers needed by [`Timeout`] and [`Retry`] have to be supplied by user.
+/// It is implemented for all functions taking `Duration` and returning a future,
+/// so e.g. `tokio::time::sleep` or `async_std::task::sleep` can be used directly.
+#[async_trait]
+pub trait Timer: Send + Sync {
+    /// Completes after `duration` elapses.
+    async fn sleep(&self, duration: Duration);
+}
+
+#[async_trait]
+impl<F, Fut> Timer for F
+where
+    F: Fn(Duration) -> Fut + Send + Sync,
+    Fut: Future<Output = ()> + Send,
+{
+    async fn sleep(&self, duration: Duration) {
+        self(duration).await
+    }
+}
+
+/// Limits time asynchronous source or provider is given to collect configuration.
+///
+/// When time runs out, collection fails with [`IoError`](crate::error::ErrorCode::IoError) of kind `TimedOut`.
+///
+///# Example
+///```rust,no_run
+///use miau::{builder::AsyncConfigurationBuilder, format, provider::Timeout, source::AsyncSource};
+///use std::time::Duration;
+///
+///async fn build(source: impl AsyncSource + Send + Sync) {
+///    let mut builder = AsyncConfigurationBuilder::default();
+///    builder.add_async(
+///        Timeout::new(source, Duration::from_secs(1), tokio::time::sleep),
+///        format::json(),
+///    );
+///}
+///```
+pub struct Timeout<T, R> {
+    inner: T,
+    duration: Duration,
+    timer: R,
+}
+
+impl<T, R: Timer> Timeout<T, R> {
+    /// Wraps `inner` source or provider so that it fails if it does not complete within `duration`.
+    pub fn new(inner: T, duration: Duration, timer: R) -> Self {
+        Timeout {
+            inner,
+            duration,
+            timer,
+        }
+    }
+
+    async fn run<O>(
+        &self,
+        operation: impl Future<Output = Result<O, ConfigurationError>> + Send,
+    ) -> Result<O, ConfigurationError> {
+        let sleep = self.timer.sleep(self.duration);
+        pin_mut!(operation, sleep);
+
+        match future::select(operation, sleep).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => {
+                let error: ConfigurationError =
+                    io::Error::new(ErrorKind::TimedOut, "operation timed out").into();
+                Err(error.enrich_with_context(format!("Timed out after {:?}", self.duration)))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S, R> AsyncSource for Timeout<S, R>
+where
+    S: AsyncSource + Send + Sync,
+    R: Timer,
+{
+    async fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        self.run(self.inner.collect()).await
+    }
+
+    fn describe(&self) -> String {
+        self.inner.describe()
+    }
+}
+
+#[async_trait]
+impl<P, R> AsyncProvider for Timeout<P, R>
+where
+    P: AsyncProvider,
+    R: Timer,
+{
+    async fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        self.run(self.inner.collect()).await
+    }
+
+    async fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        self.run(self.inner.collect_with_source_maps()).await
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        self.inner.describe()
+    }
+}
+
+/// Describes how failed collections are retried by [`Retry`].
+///
+/// Delay before retry number `n` (counting from 0) equals `initial_backoff * multiplier^n`, capped at `max_backoff`,
+/// and then randomly shortened by up to `jitter` fraction of it.
+/// Random numbers come from a simple generator seeded with current time. They are meant to spread retries
+/// of different clients apart and are not suitable for anything requiring unpredictability.
+/// By default I/O errors are retried, except for `NotFound` and `PermissionDenied`, all other errors are not.
+pub struct RetryPolicy {
+    max_retries: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: f64,
+    is_retryable: Box<dyn Fn(&ConfigurationError) -> bool + Send + Sync>,
+    random: AtomicU64,
+}
+
+impl RetryPolicy {
+    /// Creates new `RetryPolicy` retrying at most `max_retries` times.
+    ///
+    /// Backoff starts at 100 milliseconds, doubles with each retry up to 10 seconds and has jitter of 0.5.
+    pub fn new(max_retries: usize) -> Self {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.5,
+            is_retryable: Box::new(is_retryable_by_default),
+            random: AtomicU64::new(seed()),
+        }
+    }
+
+    /// Sets initial and maximal delay between retries.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sets factor by which delay grows with each retry.
+    ///
+    /// Multiplier that is not finite or is lower than `1` is treated as `1`, making delays constant.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = if multiplier.is_finite() {
+            multiplier.max(1.0)
+        } else {
+            1.0
+        };
+        self
+    }
+
+    /// Sets fraction, between 0 and 1, of delay that is randomly subtracted from it.
+    ///
+    /// Jitter of 0 makes delays deterministic. Values outside of range are clamped to it, `NaN` is treated as 0.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = if jitter.is_nan() {
+            0.0
+        } else {
+            jitter.max(0.0).min(1.0)
+        };
+        self
+    }
+
+    /// Sets function deciding whether collection failing with given error should be retried.
+    pub fn retry_if<F>(mut self, is_retryable: F) -> Self
+    where
+        F: Fn(&ConfigurationError) -> bool + Send + Sync + 'static,
+    {
+        self.is_retryable = Box::new(is_retryable);
+        self
+    }
+
+    fn delay(&self, retry: usize) -> Duration {
+        let exponential = self.initial_backoff.as_secs_f64() * self.multiplier.powi(retry as i32);
+        let capped = exponential.min(self.max_backoff.as_secs_f64());
+        let delay = capped * (1.0 - self.jitter * self.next_random());
+
+        if delay.is_finite() && delay >= 0.0 {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max_backoff
+        }
+    }
+
+    // splitmix64, returns number in range [0, 1)
+    fn next_random(&self) -> f64 {
+        const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+        let mut z = self
+            .random
+            .fetch_add(GAMMA, Ordering::Relaxed)
+            .wrapping_add(GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(3)
+    }
+}
+
+fn is_retryable_by_default(error: &ConfigurationError) -> bool {
+    match error.get_code() {
+        ErrorCode::IoError(e) => {
+            !matches!(e.kind(), ErrorKind::NotFound | ErrorKind::PermissionDenied)
+        }
+        _ => false,
+    }
+}
+
+/// Retries failed collections of asynchronous source or provider according to [`RetryPolicy`].
+///
+/// Combine it with [`Timeout`] to limit time of every attempt.
+///
+///# Example
+///```rust,no_run
+///use miau::{
+///    builder::AsyncConfigurationBuilder,
+///    format,
+///    provider::{Retry, RetryPolicy, Timeout},
+///    source::AsyncSource,
+///};
+///use std::time::Duration;
+///
+///async fn build(source: impl AsyncSource + Send + Sync) {
+///    let source = Timeout::new(source, Duration::from_secs(1), tokio::time::sleep);
+///
+///    let mut builder = AsyncConfigurationBuilder::default();
+///    builder.add_async(
+///        Retry::new(source, RetryPolicy::new(5), tokio::time::sleep),
+///        format::json(),
+///    );
+///}
+///```
+pub struct Retry<T, R> {
+    inner: T,
+    policy: RetryPolicy,
+    timer: R,
+}
+
+impl<T, R: Timer> Retry<T, R> {
+    /// Wraps `inner` source or provider so that its failed collections are retried according to `policy`.
+    pub fn new(inner: T, policy: RetryPolicy, timer: R) -> Self {
+        Retry {
+            inner,
+            policy,
+            timer,
+        }
+    }
+
+    async fn run<O, F, Fut>(&self, operation: F) -> Result<O, ConfigurationError>
+    where
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = Result<O, ConfigurationError>> + Send,
+    {
+        let mut retry = 0;
+        loop {
+            match operation().await {
+                Ok(result) => return Ok(result),
+                Err(error) => {
+                    if retry >= self.policy.max_retries || !(self.policy.is_retryable)(&error) {
+                        return Err(error.enrich_with_context(format!(
+                            "Giving up after {} attempt(s)",
+                            retry + 1
+                        )));
+                    }
+                }
+            }
+
+            self.timer.sleep(self.policy.delay(retry)).await;
+            retry += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl<S, R> AsyncSource for Retry<S, R>
+where
+    S: AsyncSource + Send + Sync,
+    R: Timer,
+{
+    async fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        self.run(|| self.inner.collect()).await
+    }
+
+    fn describe(&self) -> String {
+        self.inner.describe()
+    }
+}
+
+#[async_trait]
+impl<P, R> AsyncProvider for Retry<P, R>
+where
+    P: AsyncProvider,
+    R: Timer,
+{
+    async fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        self.run(|| self.inner.collect()).await
+    }
+
+    async fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        self.run(|| self.inner.collect_with_source_maps()).await
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        self.inner.describe()
+    }
+}
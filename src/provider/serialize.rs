@@ -0,0 +1,51 @@
+use super::Provider;
+use crate::{
+    configuration::{Configuration, ConfigurationInfo, ConfigurationTree},
+    error::ConfigurationError,
+};
+use serde::Serialize;
+
+/// Provides any value implementing [`Serialize`](serde::Serialize) as configuration.
+///
+/// It is most useful to turn `Default` implementation of configuration struct into the lowest configuration layer.
+///
+///# Example
+///```rust
+///use miau::{builder::ConfigurationBuilder, configuration::ConfigurationRead, provider::SerializeProvider};
+///use serde::Serialize;
+///
+///#[derive(Serialize, Default)]
+///struct AppConfig {
+///    port: u16,
+///    verbose: bool,
+///}
+///
+///let mut builder = ConfigurationBuilder::default();
+///builder.add_provider(SerializeProvider::new(AppConfig::default()));
+///
+///let configuration = builder.build().unwrap();
+///assert_eq!(Some(false), configuration.get("verbose"));
+///```
+pub struct SerializeProvider<T> {
+    value: T,
+}
+
+impl<T: Serialize> SerializeProvider<T> {
+    /// Creates new `SerializeProvider` from `value`.
+    pub fn new(value: T) -> Self {
+        SerializeProvider { value }
+    }
+}
+
+impl<T: Serialize> Provider for SerializeProvider<T> {
+    fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        Ok(Configuration::new_singular(
+            self.describe(),
+            ConfigurationTree::from_serialize(&self.value)?,
+        ))
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        ConfigurationInfo::new(std::any::type_name::<T>(), "serialize")
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     configuration::{Configuration, ConfigurationInfo},
     error::ConfigurationError,
-    format::Format,
+    format::{self, Format},
     source::{AsyncSource, Source},
 };
 use async_trait::async_trait;
@@ -12,6 +12,13 @@ use async_trait::async_trait;
 pub trait Provider {
     /// Collects given source into `Configuration`.
     fn collect(&self) -> Result<Configuration, ConfigurationError>;
+    /// Collects given source into `Configuration`, attaching [`SourceMap`](crate::configuration::SourceMap)s of its layers.
+    ///
+    /// It is called instead of [`collect`](Self::collect) by builders with source maps enabled.
+    /// Default implementation collects configuration without them.
+    fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        self.collect()
+    }
     /// Describes this provider.
     fn describe(&self) -> ConfigurationInfo;
 }
@@ -24,6 +31,12 @@ pub trait AsyncProvider: Send + Sync {
     /// Collects given source into `Configuration`.
     /// It uses [`async_trait`](async_trait::async_trait) which is required to implement `AsyncSource`.
     async fn collect(&self) -> Result<Configuration, ConfigurationError>;
+    /// Collects given source into `Configuration`, attaching [`SourceMap`](crate::configuration::SourceMap)s of its layers.
+    ///
+    /// Similar to [`Provider::collect_with_source_maps`].
+    async fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        self.collect().await
+    }
     /// Describes this provider.
     fn describe(&self) -> ConfigurationInfo;
 }
@@ -58,14 +71,29 @@ where
     }
 }
 
+impl<S, T: Format> ProviderStruct<S, T> {
+    fn transform(
+        &self,
+        content: Vec<u8>,
+        info: ConfigurationInfo,
+        source_map: bool,
+    ) -> Result<Configuration, ConfigurationError> {
+        let definition = format::transform_definition(&self.format, content, info, source_map)?;
+        Ok(definition.into())
+    }
+}
+
 impl<S, T> Provider for ProviderStruct<S, T>
 where
     S: Source,
     T: Format,
 {
     fn collect(&self) -> Result<Configuration, ConfigurationError> {
-        let node = self.format.transform(self.source.collect()?)?;
-        Ok(Configuration::new_singular(self.describe(), node))
+        self.transform(self.source.collect()?, self.describe(), false)
+    }
+
+    fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        self.transform(self.source.collect()?, self.describe(), true)
     }
 
     fn describe(&self) -> ConfigurationInfo {
@@ -80,8 +108,13 @@ where
     T: Format + Send + Sync,
 {
     async fn collect(&self) -> Result<Configuration, ConfigurationError> {
-        let node = self.format.transform(self.source.collect().await?)?;
-        Ok(Configuration::new_singular(self.describe(), node))
+        let content = self.source.collect().await?;
+        self.transform(content, self.describe(), false)
+    }
+
+    async fn collect_with_source_maps(&self) -> Result<Configuration, ConfigurationError> {
+        let content = self.source.collect().await?;
+        self.transform(content, self.describe(), true)
     }
 
     fn describe(&self) -> ConfigurationInfo {
@@ -1,12 +1,20 @@
 use async_trait::async_trait;
 use miau::{
     builder::{AsyncConfigurationBuilder, ConfigurationBuilder},
+    configuration::ConfigurationRead,
     error::ConfigurationError,
     format,
     format::Format,
     source::{AsyncSource, FileSource, InMemorySource, Source},
 };
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::{fs::File, io::AsyncReadExt};
 
 #[tokio::test]
@@ -148,8 +156,114 @@ async fn test_sync_to_async_builder() {
         .eq(vec![&json_desc, &json5_desc]));
 }
 
+#[tokio::test]
+async fn test_async_providers_are_collected_concurrently() {
+    let in_flight = Arc::new(InFlight::default());
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    for value in 0..5i32 {
+        builder.add_async(
+            TestDelayedSource::new(value, 5 - value as u64, in_flight.clone()),
+            format::json(),
+        );
+    }
+
+    let configuration = builder.build().await.unwrap();
+
+    assert_eq!(5, in_flight.max.load(Ordering::SeqCst));
+    assert_eq!(Some(4), configuration.get("value"));
+    assert!(configuration.infos().map(|i| i.source()).eq(vec![
+        "delayed 0",
+        "delayed 1",
+        "delayed 2",
+        "delayed 3",
+        "delayed 4"
+    ]));
+}
+
+#[tokio::test]
+async fn test_async_providers_concurrency_limit() {
+    let in_flight = Arc::new(InFlight::default());
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.max_concurrency(2);
+    for value in 0..5i32 {
+        builder.add_async(
+            TestDelayedSource::new(value, 5 - value as u64, in_flight.clone()),
+            format::json(),
+        );
+    }
+
+    let configuration = builder.build().await.unwrap();
+
+    assert_eq!(2, in_flight.max.load(Ordering::SeqCst));
+    assert_eq!(Some(4), configuration.get("value"));
+}
+
+#[tokio::test]
+async fn test_async_builder_returns_first_error_in_registration_order() {
+    let in_flight = Arc::new(InFlight::default());
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(
+        TestDelayedSource::new(0, 1, in_flight.clone()),
+        format::json(),
+    );
+    builder.add_async(
+        TestAsyncFileSource::from_path("missing_1.json"),
+        format::json(),
+    );
+    builder.add_async(
+        TestAsyncFileSource::from_path("missing_2.json"),
+        format::json(),
+    );
+
+    let error = builder.build().await.unwrap_err();
+
+    assert!(error.to_string().contains("missing_1.json"));
+}
+
 // ----------- Test implementations --------------- //
 
+#[derive(Default)]
+pub struct InFlight {
+    current: AtomicUsize,
+    max: AtomicUsize,
+}
+
+pub struct TestDelayedSource {
+    value: i32,
+    delay: u64,
+    in_flight: Arc<InFlight>,
+}
+
+impl TestDelayedSource {
+    pub fn new(value: i32, delay: u64, in_flight: Arc<InFlight>) -> Self {
+        TestDelayedSource {
+            value,
+            delay,
+            in_flight,
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncSource for TestDelayedSource {
+    async fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        let current = self.in_flight.current.fetch_add(1, Ordering::SeqCst) + 1;
+        self.in_flight.max.fetch_max(current, Ordering::SeqCst);
+
+        tokio::time::sleep(Duration::from_millis(20 * self.delay)).await;
+
+        self.in_flight.current.fetch_sub(1, Ordering::SeqCst);
+        Ok(format!(r#"{{ "value" : {} }}"#, self.value).into_bytes())
+    }
+
+    fn describe(&self) -> String {
+        format!("delayed {}", self.value)
+    }
+}
+
 pub struct TestAsyncFileSource {
     path: PathBuf,
 }
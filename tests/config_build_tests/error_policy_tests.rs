@@ -0,0 +1,144 @@
+use miau::{
+    builder::{AsyncConfigurationBuilder, ConfigurationBuilder, ErrorPolicy},
+    configuration::ConfigurationRead,
+    error::ErrorCode,
+    format,
+    source::{FileSource, InMemorySource},
+};
+
+fn add_sources(builder: &mut ConfigurationBuilder) {
+    builder.add(
+        InMemorySource::from_string_slice(r#"{"port": 80}"#),
+        format::json(),
+    );
+    builder.add(
+        FileSource::from_path("./tests/files/does_not_exist.json"),
+        format::json(),
+    );
+    builder.add(InMemorySource::from_string_slice("{"), format::json());
+    builder.add(
+        InMemorySource::from_string_slice(r#"{"host": "localhost"}"#),
+        format::json(),
+    );
+}
+
+#[test]
+fn test_fail_fast_is_default() {
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+
+    let error = builder.build().unwrap_err();
+
+    assert!(matches!(error.get_code(), ErrorCode::IoError(_)));
+}
+
+#[test]
+fn test_skip_replaces_failing_providers_with_skipped_layers() {
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+    builder.error_policy(ErrorPolicy::Skip);
+
+    let configuration = builder.build().unwrap();
+
+    let skipped: Vec<_> = configuration
+        .infos()
+        .filter(|info| info.is_skipped())
+        .collect();
+    assert_eq!(4, configuration.infos().count());
+    assert_eq!(2, skipped.len());
+    assert!(skipped[0].skip_reason().unwrap().contains("I/O error"));
+    assert!(skipped[1]
+        .skip_reason()
+        .unwrap()
+        .contains("Deserialization error"));
+
+    assert_eq!(Some(80), configuration.get("port"));
+    assert_eq!(Some("localhost"), configuration.get("host"));
+}
+
+#[test]
+fn test_collect_all_returns_all_errors() {
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+    builder.error_policy(ErrorPolicy::CollectAll);
+
+    let error = builder.build().unwrap_err();
+
+    match error.get_code() {
+        ErrorCode::MultipleErrors(errors) => {
+            assert_eq!(2, errors.len());
+            assert!(matches!(errors[0].get_code(), ErrorCode::IoError(_)));
+            assert!(matches!(
+                errors[1].get_code(),
+                ErrorCode::DeserializationError(_)
+            ));
+        }
+        other => panic!("Unexpected error {}", other),
+    }
+
+    let pretty = error.pretty_display().to_string();
+    assert!(pretty.starts_with("2 errors occurred."));
+    assert!(pretty.contains("Error 1 of 2:"));
+    assert!(pretty.contains("Error 2 of 2:"));
+    assert!(pretty.contains("does_not_exist.json"));
+}
+
+#[test]
+fn test_collect_all_returns_single_error_directly() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(InMemorySource::from_string_slice("{"), format::json());
+    builder.error_policy(ErrorPolicy::CollectAll);
+
+    let error = builder.build().unwrap_err();
+
+    assert!(matches!(
+        error.get_code(),
+        ErrorCode::DeserializationError(_)
+    ));
+    assert!(error.get_context().unwrap()[0].contains("Failed to collect configuration"));
+}
+
+#[test]
+fn test_collect_all_succeeds_without_errors() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{"port": 80}"#),
+        format::json(),
+    );
+    builder.error_policy(ErrorPolicy::CollectAll);
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some(80), configuration.get("port"));
+}
+
+#[tokio::test]
+async fn test_async_builder_keeps_error_policy_of_synchronous_builder() {
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+    builder.error_policy(ErrorPolicy::CollectAll);
+
+    let mut builder = AsyncConfigurationBuilder::from_synchronous_builder(builder);
+    let error = builder.build().await.unwrap_err();
+
+    assert!(matches!(error.get_code(), ErrorCode::MultipleErrors(ref e) if e.len() == 2));
+}
+
+#[tokio::test]
+async fn test_async_builder_skips_failing_providers() {
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add(
+        FileSource::from_path("./tests/files/does_not_exist.json"),
+        format::json(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(r#"{"port": 80}"#),
+        format::json(),
+    );
+    builder.error_policy(ErrorPolicy::Skip);
+
+    let configuration = builder.build().await.unwrap();
+
+    assert_eq!(1, configuration.infos().filter(|i| i.is_skipped()).count());
+    assert_eq!(Some(80), configuration.get("port"));
+}
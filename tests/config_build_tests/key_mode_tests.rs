@@ -0,0 +1,250 @@
+use miau::{
+    builder::{AsyncConfigurationBuilder, ConfigurationBuilder},
+    configuration::{Configuration, ConfigurationInfo, ConfigurationRead, Key, KeyMode, NodeType},
+    error::ErrorCode,
+    format,
+    provider::EnvironmentProvider,
+    source::InMemorySource,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Database {
+    host: String,
+    max_connections: u32,
+    #[serde(rename = "readOnly")]
+    read_only: bool,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Backend {
+    Postgres { port: u16 },
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Settings {
+    db: Database,
+    backend: Backend,
+}
+
+fn add_sources(builder: &mut ConfigurationBuilder) {
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{
+                "db" : { "host" : "localhost", "max-connections" : 4, "readOnly" : false },
+                "backend" : { "Postgres" : { "port" : 5432 } }
+            }"#,
+        ),
+        format::json(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "DB" : { "HOST" : "db.internal", "MAX_CONNECTIONS" : 16 }, "BACKEND" : { "POSTGRES" : { "PORT" : 6432 } } }"#,
+        ),
+        format::json(),
+    );
+}
+
+#[test]
+fn test_keys_are_exact_by_default() {
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some("localhost".to_string()), configuration.get("db:host"));
+    assert_eq!(
+        Some("db.internal".to_string()),
+        configuration.get("DB:HOST")
+    );
+    assert!(configuration.try_convert_into::<Settings>().is_err());
+}
+
+#[test]
+fn test_ignoring_case_of_keys() {
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+    builder.key_mode(KeyMode::IgnoreCase);
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(KeyMode::IgnoreCase, configuration.key_mode());
+    assert_eq!(
+        Some("db.internal".to_string()),
+        configuration.get("db:host")
+    );
+    assert_eq!(
+        Some("db.internal".to_string()),
+        configuration.get("Db:Host")
+    );
+    assert_eq!(Some(4), configuration.get("db:MAX-CONNECTIONS"));
+    assert_eq!(Some(16), configuration.get("db:max_connections"));
+
+    let lens = configuration.lens().try_lens("DB").unwrap();
+    assert_eq!(Some(false), lens.get("READONLY"));
+    assert_eq!(
+        vec!["host", "max-connections", "max_connections", "readonly"],
+        lens.keys("")
+            .unwrap()
+            .into_iter()
+            .map(|k| k.to_string())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_ignoring_case_and_separators_of_keys() {
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+    builder.key_mode(KeyMode::IgnoreCaseAndSeparators);
+    builder.source_maps(true);
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some(16), configuration.get("db:max-connections"));
+    assert!(configuration.locate("DB:Read-Only").unwrap().is_none());
+    assert!(configuration.locate("DB:ReadOnly").unwrap().is_some());
+
+    let db: Database = configuration.get_deserialized("Db").unwrap().unwrap();
+    assert_eq!(
+        Database {
+            host: "db.internal".into(),
+            max_connections: 16,
+            read_only: false,
+        },
+        db
+    );
+
+    let settings: Settings = configuration.try_convert_into_denying_unknown().unwrap();
+    assert_eq!(Backend::Postgres { port: 6432 }, settings.backend);
+}
+
+#[test]
+fn test_merging_owned_trees_with_ignored_case() {
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+    builder.key_mode(KeyMode::IgnoreCaseAndSeparators);
+
+    let configuration = builder.build().unwrap();
+    let settings: Settings = configuration.try_convert_into().unwrap();
+
+    assert_eq!("db.internal", settings.db.host);
+    assert_eq!(16, settings.db.max_connections);
+}
+
+#[test]
+fn test_override_layers_are_normalized() {
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+    builder.key_mode(KeyMode::IgnoreCase);
+
+    let mut configuration = builder.build().unwrap();
+
+    let root = serde_json::from_str(r#"{ "Db" : { "Host" : "override" } }"#).unwrap();
+    configuration
+        .push_override(ConfigurationInfo::new("tests", "none"), root)
+        .unwrap();
+
+    assert_eq!(Some("override".to_string()), configuration.get("DB:HOST"));
+}
+
+#[test]
+fn test_keys_equal_after_normalization_are_merged() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "Db" : { "host" : "localhost" }, "db" : { "port" : 5432 } }"#,
+        ),
+        format::json(),
+    );
+    builder.key_mode(KeyMode::IgnoreCase);
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some("localhost".to_string()), configuration.get("db:host"));
+    assert_eq!(Some(5432), configuration.get("DB:PORT"));
+}
+
+#[test]
+fn test_keys_equal_after_normalization_with_incompatible_values() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "servers" : [ { "Db" : { "host" : "localhost" }, "db" : "postgres" } ] }"#,
+        ),
+        format::json(),
+    );
+    builder.key_mode(KeyMode::IgnoreCase);
+
+    let error = builder.build().unwrap_err();
+
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::BadNodeMerge(NodeType::Map, NodeType::Value)
+    ));
+    assert_eq!(
+        &[
+            Key::Map("db".into()),
+            Key::Array(0),
+            Key::Map("servers".into())
+        ],
+        error.get_path().unwrap()
+    );
+
+    let root = serde_json::from_str(r#"{ "PORT" : 1, "port" : [] }"#).unwrap();
+    let mut configuration = Configuration::default()
+        .with_key_mode(KeyMode::IgnoreCase)
+        .unwrap();
+    assert!(configuration
+        .push_override(ConfigurationInfo::new("tests", "none"), root)
+        .is_err());
+}
+
+#[test]
+fn test_environment_variables_override_nested_keys() {
+    std::env::set_var("KEY_MODE_TEST_DB_HOST", "db.env");
+
+    let mut builder = ConfigurationBuilder::default();
+    add_sources(&mut builder);
+    builder.add_provider(
+        EnvironmentProvider::with_prefix("KEY_MODE_TEST_")
+            .strip_prefix(true)
+            .separator("_"),
+    );
+    builder.key_mode(KeyMode::IgnoreCaseAndSeparators);
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some("db.env".to_string()), configuration.get("db:host"));
+
+    let db: Database = configuration.get_deserialized("db").unwrap().unwrap();
+    assert_eq!(
+        Database {
+            host: "db.env".into(),
+            max_connections: 16,
+            read_only: false,
+        },
+        db
+    );
+}
+
+#[tokio::test]
+async fn test_async_builder_uses_key_mode() {
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "db" : { "host" : "localhost" } }"#),
+        format::json(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "DB" : { "HOST" : "db.internal" } }"#),
+        format::json(),
+    );
+    builder.key_mode(KeyMode::IgnoreCase);
+
+    let configuration = builder.build().await.unwrap();
+
+    assert_eq!(
+        Some("db.internal".to_string()),
+        configuration.get("db:host")
+    );
+}
@@ -20,7 +20,4 @@ fn build_tree_manually() {
     );
 
     let _cfg = ConfigurationTree::Map(root);
-
-    // we got here we are all right!
-    assert!(true)
 }
@@ -1,2 +1,4 @@
 mod async_builder_tests;
+mod error_policy_tests;
+mod key_mode_tests;
 mod manual_build_tests;
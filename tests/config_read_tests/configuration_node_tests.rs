@@ -1,13 +1,16 @@
 use miau::{
     builder::ConfigurationBuilder,
-    configuration::ConfigurationRead,
+    configuration::{
+        ConfigurationInfo, ConfigurationRead, ConfigurationTree, Key, NodeType, Value,
+    },
     error::{ConfigurationError, ErrorCode},
     format::Json,
     source::InMemorySource,
 };
 use serde::Deserialize;
+use std::convert::TryInto;
 
-static TEST_JSON: &'static str = r#"
+static TEST_JSON: &str = r#"
 {
     "map": {
         "array1" : [1,23],
@@ -74,9 +77,190 @@ fn test_single_node_read_result() {
     ));
 }
 
+#[test]
+fn test_enumerating_children_of_node() {
+    let tree: ConfigurationTree = serde_json::from_str(TEST_JSON).unwrap();
+
+    assert_eq!(
+        vec![Key::Map("array1".into()), Key::Map("entry".into())],
+        tree.keys("map").unwrap()
+    );
+    assert_eq!(2, tree.len("map:array1").unwrap());
+    assert_eq!(Some(NodeType::Array), tree.node_type("map:array1").unwrap());
+    assert_eq!(None, tree.node_type("map:array1:[2]").unwrap());
+
+    let map = tree.entries().next().unwrap().1;
+    let names: Vec<&str> = map.entries().map(|(name, _)| name).collect();
+    assert_eq!(vec!["array1", "entry"], names);
+    assert_eq!(0, map.elements().count());
+
+    let array = map.entries().next().unwrap().1;
+    let values: Vec<Option<i32>> = array.elements().map(|e| e.get_value().unwrap()).collect();
+    assert_eq!(vec![Some(1), Some(23)], values);
+
+    let keys: Vec<Key> = array.children().map(|(key, _)| key).collect();
+    assert_eq!(vec![Key::Array(0), Key::Array(1)], keys);
+}
+
+#[test]
+fn test_flatten_and_unflatten_tree() {
+    let tree: ConfigurationTree = serde_json::from_str(TEST_JSON).unwrap();
+
+    let keys: Vec<String> = tree.flatten().map(|(key, _)| key.to_string()).collect();
+    assert_eq!(
+        vec![
+            "map:array1:[0]",
+            "map:array1:[1]",
+            "map:entry:value1",
+            "map:entry:value2:array:[0]",
+            "map:entry:value2:array:[1]",
+            "map:entry:value2:value3",
+            "map:entry:value3",
+        ],
+        keys
+    );
+
+    let rebuilt =
+        ConfigurationTree::from_flat(tree.flatten().map(|(key, value)| (key, value.clone())))
+            .unwrap();
+    assert_eq!(tree, rebuilt);
+}
+
+#[test]
+fn test_unflatten_fills_array_gaps_and_replaces_duplicates() {
+    let tree = ConfigurationTree::from_flat(vec![
+        ("array:[2]".try_into().unwrap(), Value::SignedInteger(3)),
+        ("value".try_into().unwrap(), Value::Bool(false)),
+        ("value".try_into().unwrap(), Value::Bool(true)),
+    ])
+    .unwrap();
+
+    assert_eq!(3, tree.len("array").unwrap());
+    let empty: Option<i32> = tree.get_result("array:[0]").unwrap();
+    assert_eq!(None, empty);
+    assert_eq!(Some(3), tree.get("array:[2]"));
+    assert_eq!(Some(true), tree.get("value"));
+}
+
+#[test]
+fn test_unflatten_rejects_huge_array_indices() {
+    for index in &[usize::MAX, 4_000_000_000] {
+        let key = vec![Key::Map("array".into()), Key::Array(*index)];
+        let error =
+            ConfigurationTree::from_flat(vec![(key.into(), Value::SignedInteger(1))]).unwrap_err();
+
+        assert!(std::matches!(
+            error.get_code(),
+            ErrorCode::IndexOutOfRange(i) if i == index
+        ));
+        assert!(error.to_string().contains("array"));
+    }
+
+    // gaps are measured from elements inserted so far
+    let tree = ConfigurationTree::from_flat(vec![
+        ("array:[1000]".try_into().unwrap(), Value::SignedInteger(1)),
+        ("array:[2000]".try_into().unwrap(), Value::SignedInteger(2)),
+    ])
+    .unwrap();
+    assert_eq!(Some(2), tree.get("array:[2000]"));
+}
+
+#[test]
+fn test_setting_values_creates_missing_nodes() {
+    let mut tree: ConfigurationTree = serde_json::from_str(TEST_JSON).unwrap();
+
+    tree.set("map:entry:value1", false).unwrap();
+    tree.set("map:array1:[2]", 45).unwrap();
+    tree.set("map:new:[0]:name", "first").unwrap();
+    tree.set("map:new:[1]", Value::Float(1.5)).unwrap();
+
+    assert_eq!(Some(false), tree.get("map:entry:value1"));
+    assert_eq!(Some(45), tree.get("map:array1:[2]"));
+    assert_eq!(Some("first"), tree.get("map:new:[0]:name"));
+    assert_eq!(Some(1.5), tree.get("map:new:[1]"));
+
+    *tree.entry("map:entry:value3").unwrap() = ConfigurationTree::Value(None);
+    let value3: Option<String> = tree.get_result("map:entry:value3").unwrap();
+    assert_eq!(None, value3);
+
+    let created = tree.entry("map:created").unwrap();
+    assert_eq!(&ConfigurationTree::Value(None), created);
+}
+
+#[test]
+fn test_removing_nodes() {
+    let mut tree: ConfigurationTree = serde_json::from_str(TEST_JSON).unwrap();
+
+    let removed = tree.remove("map:array1:[0]").unwrap();
+    assert_eq!(Some(ConfigurationTree::from(1)), removed);
+    assert_eq!(Some(23), tree.get("map:array1:[0]"));
+    assert_eq!(1, tree.len("map:array1").unwrap());
+
+    let removed = tree.remove("map:entry:value2").unwrap().unwrap();
+    assert_eq!(Some(NodeType::Map), removed.node_type("").unwrap());
+    assert_eq!(None, tree.node_type("map:entry:value2").unwrap());
+
+    assert_eq!(None, tree.remove("map:entry:value2").unwrap());
+    assert_eq!(None, tree.remove("map:entry:missing:deeper").unwrap());
+    assert_eq!(None, tree.remove("map:array1:[5]").unwrap());
+
+    let root = tree.remove("").unwrap().unwrap();
+    assert_eq!(Some(NodeType::Map), root.node_type("").unwrap());
+    assert_eq!(ConfigurationTree::Value(None), tree);
+}
+
+#[test]
+fn test_negative_indices_and_slices_in_tree() {
+    let mut tree: ConfigurationTree = serde_json::from_str(TEST_JSON).unwrap();
+
+    assert_eq!(Some(23), tree.get("map:array1:[-1]"));
+    assert_eq!(Some(1), tree.get("map:array1:[-2]"));
+    assert_eq!(Some(2), tree.get("map:entry:value2:array:[-1]"));
+
+    let tail: Vec<i32> = tree.get_deserialized("map:array1:[-1..]").unwrap().unwrap();
+    assert_eq!(vec![23], tail);
+    let all: Vec<i32> = tree.get_deserialized("map:array1:[..]").unwrap().unwrap();
+    assert_eq!(vec![1, 23], all);
+    assert_eq!(1, tree.len("map:array1:[1..5]").unwrap());
+
+    tree.set("map:array1:[-1]", 24).unwrap();
+    assert_eq!(Some(24), tree.get("map:array1:[1]"));
+
+    let removed = tree.remove("map:array1:[-2]").unwrap();
+    assert_eq!(Some(ConfigurationTree::from(1)), removed);
+    assert_eq!(Some(24), tree.get("map:array1:[-1]"));
+    assert_eq!(None, tree.remove("map:array1:[-2]").unwrap());
+}
+
+#[test]
+fn test_push_override_layer() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(TEST_JSON.trim()),
+        Json::default(),
+    );
+    let mut configuration = builder.build().unwrap();
+
+    let mut root = ConfigurationTree::Value(None);
+    root.set("map:entry:value3", "overridden").unwrap();
+    configuration
+        .push_override(ConfigurationInfo::new("admin", "programmatic"), root)
+        .unwrap();
+
+    assert_eq!(
+        Some("overridden".to_string()),
+        configuration.get("map:entry:value3")
+    );
+    assert_eq!(Some(true), configuration.get("map:entry:value1"));
+    assert_eq!(
+        Some("admin"),
+        configuration.infos().last().map(|i| i.source())
+    );
+}
+
 // ---------- Strongly typed conversion tests ---------------- //
 
-static TEST_JSON_2: &'static str = r#"
+static TEST_JSON_2: &str = r#"
 {
     "array" : [1,2],
     "value3": "a"
@@ -101,7 +285,7 @@ fn test_singular_configuration_into_struct() {
 
     let config = configuration.try_convert_into::<Config>().unwrap();
 
-    assert!(vec![1, 2].iter().eq(config.array.iter()));
+    assert!([1, 2].iter().eq(config.array.iter()));
     assert_eq!("a", config.value3);
     assert_eq!(None, config.optional);
 }
@@ -154,6 +338,26 @@ fn test_node_index_out_of_range() {
     assert!(error_string.contains("map-->entry-->value2-->array-->[66]"))
 }
 
+#[test]
+fn test_node_negative_index_out_of_range() {
+    let tree: ConfigurationTree = serde_json::from_str(TEST_JSON).unwrap();
+
+    let result: Result<Option<i32>, ConfigurationError> = tree.get_result("map:array1:[-3]");
+    let error = result.unwrap_err();
+
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::IndexOutOfRange(..)
+    ));
+    assert!(error.to_string().contains("map-->array1-->[-3]"));
+
+    let result: Result<Option<i32>, ConfigurationError> = tree.get_result("map:array1:[0..1]");
+    assert!(std::matches!(
+        result.unwrap_err().get_code(),
+        ErrorCode::MisplacedSlice(..)
+    ));
+}
+
 #[test]
 fn test_node_key_not_found() {
     let mut builder = ConfigurationBuilder::default();
@@ -224,3 +428,58 @@ fn test_node_key_and_node_mismatch_descending() {
     let error_string = error.to_string();
     assert!(error_string.contains("map-->array1-->one"));
 }
+
+#[test]
+fn test_unflatten_conflicting_keys() {
+    let error = ConfigurationTree::from_flat(vec![
+        ("map:array:[0]".try_into().unwrap(), Value::SignedInteger(1)),
+        ("map:array:key".try_into().unwrap(), Value::SignedInteger(2)),
+    ])
+    .unwrap_err();
+
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::BadNodeMerge(NodeType::Array, NodeType::Map)
+    ));
+    let error_string = error.to_string();
+    assert!(error_string.contains("map-->array"));
+    assert!(error_string.contains("map:array:key"));
+
+    let error = ConfigurationTree::from_flat(vec![
+        ("map:value".try_into().unwrap(), Value::SignedInteger(1)),
+        ("map".try_into().unwrap(), Value::SignedInteger(2)),
+    ])
+    .unwrap_err();
+
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::BadNodeMerge(NodeType::Map, NodeType::Value)
+    ));
+}
+
+#[test]
+fn test_mutation_through_incompatible_nodes() {
+    let mut tree: ConfigurationTree = serde_json::from_str(TEST_JSON).unwrap();
+
+    let error = tree.set("map:entry:value1:inner", 1).unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::WrongNodeType(NodeType::Map, NodeType::Value)
+    ));
+    assert!(error.to_string().contains("map-->entry-->value1-->inner"));
+
+    let error = tree.set("map:array1:[3]", 1).unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::IndexOutOfRange(3)
+    ));
+
+    let error = tree.set("map:array1:key", 1).unwrap_err();
+    assert!(std::matches!(error.get_code(), ErrorCode::WrongKeyType(..)));
+
+    let error = tree.remove("map:entry:value1:[0]").unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::WrongNodeType(NodeType::Array, NodeType::Value)
+    ));
+}
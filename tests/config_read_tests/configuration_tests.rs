@@ -181,8 +181,8 @@ fn test_single_map_entry_config_build_json_different_type() {
 
     let mut builder = ConfigurationBuilder::default();
     let result = builder
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_1).unwrap())
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_2).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_1).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_2).unwrap())
         .build()
         .unwrap();
 
@@ -197,8 +197,8 @@ fn test_two_different_map_entries_config_build_json() {
 
     let mut builder = ConfigurationBuilder::default();
     let result = builder
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_1).unwrap())
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_2).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_1).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_2).unwrap())
         .build()
         .unwrap();
 
@@ -213,8 +213,8 @@ fn test_single_array_entry_config_build_json() {
 
     let mut builder = ConfigurationBuilder::default();
     let result = builder
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_1).unwrap())
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_2).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_1).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_2).unwrap())
         .build()
         .unwrap();
 
@@ -262,8 +262,8 @@ fn test_complex_map_config_build_json() {
 
     let mut builder = ConfigurationBuilder::default();
     let result = builder
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_1).unwrap())
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_2).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_1).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_2).unwrap())
         .build()
         .unwrap();
 
@@ -298,8 +298,8 @@ fn test_array_of_structs_build_json() {
 
     let mut builder = ConfigurationBuilder::default();
     let result = builder
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_1).unwrap())
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_2).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_1).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_2).unwrap())
         .build()
         .unwrap();
 
@@ -331,8 +331,8 @@ fn test_structs_of_arrays_build_json() {
 
     let mut builder = ConfigurationBuilder::default();
     let result = builder
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_1).unwrap())
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_2).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_1).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_2).unwrap())
         .build()
         .unwrap();
 
@@ -379,8 +379,8 @@ fn test_triple_nested_map_build() {
 
     let mut builder = ConfigurationBuilder::default();
     let result = builder
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_1).unwrap())
-        .add_provider(serde_json::from_str::<Configuration>(&config_str_2).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_1).unwrap())
+        .add_provider(serde_json::from_str::<Configuration>(config_str_2).unwrap())
         .build()
         .unwrap();
 
@@ -422,6 +422,68 @@ fn test_get_result_wrong_key_type() {
     assert_eq!(None, value);
 }
 
+#[test]
+fn test_get_result_strict_does_not_skip_value_of_wrong_type() {
+    let json1 = r#"{"key" : { "flag" : true, "count" : 3 }}"#;
+    let json2 = r#"{"key" : { "flag" : "1", "count" : 3.9 }}"#;
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(InMemorySource::from_string_slice(json1), Json::new());
+    builder.add(InMemorySource::from_string_slice(json2), Json::new());
+
+    let configuration = builder.build().unwrap();
+
+    let lenient = ConfigurationRead::<'_, bool, &str>::get(&configuration, "key:flag");
+    assert_eq!(Some(true), lenient);
+
+    let error = configuration
+        .get_result_strict::<bool, _>("key:flag")
+        .unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::WrongValueType(..)
+    ));
+    assert!(error.to_string().contains("Value defined in"));
+
+    let error = configuration
+        .get_result_strict::<i32, _>("key:count")
+        .unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::WrongValueType(..)
+    ));
+
+    assert_eq!(None, configuration.get_strict::<i32, _>("key:count"));
+    assert_eq!(Some(3.9), configuration.get_strict::<f64, _>("key:count"));
+    assert_eq!(
+        None,
+        configuration
+            .get_result_strict::<bool, _>("key:missing")
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_get_result_strict_through_lens() {
+    let json = r#"{"key" : { "name" : "app", "port" : 80 }}"#;
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(InMemorySource::from_string_slice(json), Json::new());
+
+    let configuration = builder.build().unwrap();
+    let lens = configuration.lens().try_lens("key").unwrap();
+
+    assert_eq!(Some("app"), lens.get_strict::<&str, _>("name"));
+    assert_eq!(Some(80), lens.get_strict::<i32, _>("port"));
+
+    let error = lens.get_result_strict::<String, _>("port").unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::WrongValueType(..)
+    ));
+    assert!(error.to_string().contains("port"));
+}
+
 #[test]
 fn test_get_result_key_unparsable() {
     let json1 = r#"{"key" : { "key" : "not_a_number" }}"#;
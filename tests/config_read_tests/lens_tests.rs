@@ -1,10 +1,13 @@
 use miau::{
-    builder::ConfigurationBuilder, configuration::ConfigurationRead, error::ErrorCode,
-    format::Json, source::InMemorySource,
+    builder::ConfigurationBuilder,
+    configuration::{ConfigurationRead, Key, NodeType},
+    error::ErrorCode,
+    format::Json,
+    source::InMemorySource,
 };
 use serde::Deserialize;
 
-static TEST_JSON: &'static str = r#"
+static TEST_JSON: &str = r#"
 {
     "map": {
         "entry": {
@@ -19,6 +22,11 @@ static TEST_JSON: &'static str = r#"
 
 // -------------- Happy path tests ------------------------- //
 
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    x: i32,
+}
+
 #[derive(Debug, Deserialize)]
 struct Config {
     array: Vec<i32>,
@@ -40,7 +48,6 @@ fn test_basic_lens() {
     assert_eq!(Some(true), lens.get("value1"));
     assert_eq!(Some("true"), lens.get("value1"));
     assert_eq!(Some(1), lens.get("value2:array:[0]"));
-    assert!(true)
 }
 
 #[test]
@@ -105,6 +112,265 @@ fn test_lensing_with_multiple_configs() {
     );
 }
 
+#[test]
+fn test_lens_enumerates_union_of_children() {
+    let json1 = r#"{"servers" : { "eu" : { "port" : 1 }, "us" : { "port" : 2 } }, "hosts" : ["a", "b", "c"]}"#;
+    let json2 =
+        r#"{"servers" : { "asia" : { "port" : 3 }, "us" : { "port" : 4 } }, "hosts" : ["d"]}"#;
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(InMemorySource::from_string_slice(json1), Json::default());
+    builder.add(InMemorySource::from_string_slice(json2), Json::default());
+
+    let configuration = builder.build().unwrap();
+
+    let keys = configuration.keys("servers").unwrap();
+    assert_eq!(
+        vec![
+            Key::Map("asia".into()),
+            Key::Map("eu".into()),
+            Key::Map("us".into())
+        ],
+        keys
+    );
+    assert_eq!(3, configuration.len("hosts").unwrap());
+    assert_eq!(0, configuration.len("hosts:[0]").unwrap());
+    assert_eq!(0, configuration.len("missing").unwrap());
+
+    assert_eq!(
+        Some(NodeType::Map),
+        configuration.node_type("servers").unwrap()
+    );
+    assert_eq!(
+        Some(NodeType::Array),
+        configuration.node_type("hosts").unwrap()
+    );
+    assert_eq!(
+        Some(NodeType::Value),
+        configuration.node_type("hosts:[0]").unwrap()
+    );
+    assert_eq!(None, configuration.node_type("missing").unwrap());
+
+    let ports: Vec<(String, Option<i32>)> = configuration
+        .lens()
+        .try_lens("servers")
+        .unwrap()
+        .entries()
+        .map(|(name, lens)| (name, lens.get("port")))
+        .collect();
+    assert_eq!(
+        vec![
+            ("asia".to_string(), Some(3)),
+            ("eu".to_string(), Some(1)),
+            ("us".to_string(), Some(4))
+        ],
+        ports
+    );
+
+    let hosts: Vec<Option<String>> = configuration
+        .lens()
+        .try_lens("hosts")
+        .unwrap()
+        .elements()
+        .map(|lens| lens.get(""))
+        .collect();
+    assert_eq!(
+        vec![Some("d".into()), Some("b".into()), Some("c".into())],
+        hosts
+    );
+
+    let children: Vec<Key> = configuration.children().map(|(key, _)| key).collect();
+    assert_eq!(
+        vec![Key::Map("hosts".into()), Key::Map("servers".into())],
+        children
+    );
+    assert_eq!(0, configuration.elements().count());
+}
+
+#[test]
+fn test_lens_enumeration_fails_for_unparsable_key() {
+    let configuration = ConfigurationBuilder::default().build().unwrap();
+
+    let error = configuration.lens().keys("map:[A]").unwrap_err();
+    assert!(std::matches!(error.get_code(), ErrorCode::ParsingError(..)));
+}
+
+#[test]
+fn test_querying_with_wildcards_across_layers() {
+    let json1 = r#"{
+        "services" : { "web" : { "port" : 80 }, "db" : { "port" : 5432, "host" : "db" } },
+        "routes" : [ { "path" : "/" }, { "path" : "/api" } ]
+    }"#;
+    let json2 = r#"{
+        "services" : { "cache" : { "port" : 6379 }, "web" : { "port" : 8080 } },
+        "routes" : [ { "path" : "/home" } ]
+    }"#;
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(InMemorySource::from_string_slice(json1), Json::default());
+    builder.add(InMemorySource::from_string_slice(json2), Json::default());
+
+    let configuration = builder.build().unwrap();
+
+    let ports: Vec<(String, i32)> = configuration
+        .query_values("services:*:port")
+        .unwrap()
+        .into_iter()
+        .map(|(key, port)| (key.to_string(), port))
+        .collect();
+    assert_eq!(
+        vec![
+            ("services:cache:port".to_string(), 6379),
+            ("services:db:port".to_string(), 5432),
+            ("services:web:port".to_string(), 8080),
+        ],
+        ports
+    );
+
+    let paths: Vec<String> = configuration
+        .query_values("routes:[*]:path")
+        .unwrap()
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect();
+    assert_eq!(vec!["/home", "/api"], paths);
+
+    let hosts: Vec<String> = configuration
+        .query("**:host")
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key.to_string())
+        .collect();
+    assert_eq!(vec!["services:db:host"], hosts);
+
+    let services = configuration.lens().try_lens("services").unwrap();
+    let all: Vec<String> = services
+        .query("**")
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key.to_string())
+        .collect();
+    assert_eq!(
+        vec![
+            "",
+            "cache",
+            "cache:port",
+            "db",
+            "db:host",
+            "db:port",
+            "web",
+            "web:port"
+        ],
+        all
+    );
+
+    assert!(configuration.query("services:*:[*]").unwrap().is_empty());
+    assert!(configuration.query("missing:**").unwrap().is_empty());
+}
+
+#[test]
+fn test_query_fails_for_invalid_wildcard() {
+    let configuration = ConfigurationBuilder::default().build().unwrap();
+
+    let error = configuration.query("services:*s").unwrap_err();
+    assert!(std::matches!(error.get_code(), ErrorCode::ParsingError(..)));
+    assert!(error.to_string().contains("services:*s"));
+}
+
+#[test]
+fn test_negative_indices_resolve_against_merged_arrays() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "hosts" : ["a", "b", "c"] }"#),
+        Json::default(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "hosts" : ["x"] }"#),
+        Json::default(),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some("c".to_string()), configuration.get("hosts:[-1]"));
+    assert_eq!(Some("x".to_string()), configuration.get("hosts:[-3]"));
+    let missing: Option<String> = configuration.get("hosts:[-4]");
+    assert_eq!(None, missing);
+
+    let lens = configuration.lens().try_lens("hosts:[-2]").unwrap();
+    assert_eq!(Some("b".to_string()), lens.get(""));
+}
+
+#[test]
+fn test_lens_into_slice() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "servers" : [ { "x" : 0 }, { "x" : 1 }, { "x" : 2 }, { "x" : 3 } ] }"#,
+        ),
+        Json::default(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "servers" : [ { "x" : 10 }, { "x" : 11 } ] }"#),
+        Json::default(),
+    );
+
+    let configuration = builder.build().unwrap();
+    let lens = configuration.lens().try_lens("servers:[1..3]").unwrap();
+
+    assert_eq!(2, lens.len("").unwrap());
+    assert_eq!(Some(NodeType::Array), lens.node_type("").unwrap());
+    assert_eq!(Some(11), lens.get("[0]:x"));
+    assert_eq!(Some(2), lens.get("[-1]:x"));
+    let outside: Option<i32> = lens.get("[2]:x");
+    assert_eq!(None, outside);
+
+    let xs: Vec<Option<i32>> = lens.elements().map(|e| e.get("x")).collect();
+    assert_eq!(vec![Some(11), Some(2)], xs);
+
+    let nested = lens.try_lens("[1..]").unwrap();
+    assert_eq!(1, nested.len("").unwrap());
+    assert_eq!(Some(2), nested.get("[0]:x"));
+
+    let sliced: Vec<Server> = lens.try_convert_into().unwrap();
+    assert_eq!(vec![Server { x: 11 }, Server { x: 2 }], sliced);
+
+    let tail: Vec<Server> = configuration
+        .get_deserialized("servers:[-2..]")
+        .unwrap()
+        .unwrap();
+    assert_eq!(vec![Server { x: 2 }, Server { x: 3 }], tail);
+
+    assert_eq!(0, configuration.len("servers:[5..]").unwrap());
+    assert_eq!(0, configuration.len("servers:[0]:[..]").unwrap());
+}
+
+#[test]
+fn test_querying_with_negative_indices_and_slices() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "routes" : [ "/", "/api", "/admin" ] }"#),
+        Json::default(),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    let last: Vec<(String, String)> = configuration
+        .query_values("routes:[-1]")
+        .unwrap()
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+    assert_eq!(vec![("routes:[2]".to_string(), "/admin".to_string())], last);
+
+    let sliced: Vec<String> = configuration
+        .query("routes:[..2]")
+        .unwrap()
+        .into_iter()
+        .map(|(k, _)| k.to_string())
+        .collect();
+    assert_eq!(vec!["routes:[0]", "routes:[1]"], sliced);
+}
+
 // ----------------- Strongly typed tests ------------------------- //
 
 #[test]
@@ -120,10 +386,9 @@ fn test_lens_into_struct() {
 
     let config = lens.try_convert_into::<Config>().unwrap();
 
-    assert!(vec![1, 2].iter().eq(config.array.iter()));
+    assert!([1, 2].iter().eq(config.array.iter()));
     assert_eq!("a", config.value3);
     assert_eq!(None, config.optional);
-    assert!(true)
 }
 
 // --------------- Failure tests ---------------------- //
@@ -138,3 +403,35 @@ fn test_lens_key_unparsable() {
     assert!(std::matches!(error.get_code(), ErrorCode::ParsingError(..)));
     assert!(error.to_string().contains("drooids:[a]"));
 }
+
+#[test]
+fn test_slice_can_only_be_the_last_key() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "a" : [ { "b" : 1 } ] }"#),
+        Json::default(),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    let error = configuration.lens().try_lens("a:[0..1]:b").unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::MisplacedSlice(..)
+    ));
+    assert!(error.to_string().contains("[0..1]"));
+
+    let result: Result<Option<i32>, _> = configuration.get_result("a:[..]:b");
+    let error = result.unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::MisplacedSlice(..)
+    ));
+
+    let lens = configuration.lens().try_lens("a:[..]").unwrap();
+    let error = lens.try_lens("[..]:b").unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::MisplacedSlice(..)
+    ));
+}
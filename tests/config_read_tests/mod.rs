@@ -1,3 +1,5 @@
 mod configuration_node_tests;
 mod configuration_tests;
 mod lens_tests;
+mod owned_lens_tests;
+mod snapshot_tests;
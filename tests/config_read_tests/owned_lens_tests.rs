@@ -0,0 +1,144 @@
+use miau::{
+    builder::ConfigurationBuilder,
+    configuration::{Configuration, ConfigurationRead, OwnedLens},
+    error::ErrorCode,
+    format,
+    source::InMemorySource,
+};
+use serde::Deserialize;
+use std::{sync::Arc, thread};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+fn configuration() -> Configuration {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(
+            "{\n  \"servers\" : [\n    { \"host\" : \"a\", \"port\" : 1 },\n    { \"host\" : \"b\", \"port\" : 2 },\n    { \"host\" : \"c\", \"port\" : 3 }\n  ]\n}",
+        ),
+        format::json(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "servers" : [ { "port" : 10 } ] }"#),
+        format::json(),
+    );
+    builder.source_maps(true);
+
+    builder.build().unwrap()
+}
+
+fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+
+#[test]
+fn test_owned_lens_is_shareable() {
+    assert_shareable::<OwnedLens>();
+}
+
+#[test]
+fn test_owned_lens_reads_in_other_threads() {
+    let lens = OwnedLens::from(configuration());
+    let servers = lens.try_lens("servers").unwrap();
+
+    let handles: Vec<_> = (0..3)
+        .map(|index| {
+            let servers = servers.clone();
+            thread::spawn(move || {
+                let port: Option<i32> = servers.get(format!("[{}]:port", index).as_str());
+                port
+            })
+        })
+        .collect();
+
+    let ports: Vec<Option<i32>> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(vec![Some(10), Some(2), Some(3)], ports);
+}
+
+#[test]
+fn test_owned_lens_converts_into_struct() {
+    let lens = OwnedLens::new(Arc::new(configuration()));
+
+    let first: Server = lens
+        .try_lens("servers:[0]")
+        .unwrap()
+        .try_convert_into()
+        .unwrap();
+    assert_eq!(
+        Server {
+            host: "a".into(),
+            port: 10
+        },
+        first
+    );
+
+    let tail: Vec<Server> = lens
+        .try_lens("servers:[-2..]")
+        .unwrap()
+        .try_convert_into()
+        .unwrap();
+    assert_eq!(
+        vec!["b", "c"],
+        tail.iter().map(|s| s.host.as_str()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_owned_lens_created_from_lens() {
+    let configuration = configuration();
+
+    let owned = {
+        let lens = configuration.lens().try_lens("servers:[1..]").unwrap();
+        lens.to_owned_lens()
+    };
+    drop(configuration);
+
+    assert_eq!(2, owned.lens().len("").unwrap());
+    let host: Option<String> = owned.get("[0]:host");
+    assert_eq!(Some("b".to_string()), host);
+
+    let last = owned.try_lens("[-1]").unwrap();
+    let port: Option<i32> = last.get("port");
+    assert_eq!(Some(3), port);
+
+    // locations still point at the original source
+    let location = last.lens().locate("host").unwrap().unwrap();
+    assert_eq!(5, location.line());
+}
+
+#[test]
+fn test_owned_lens_into_missing_keys_shares_configuration() {
+    let configuration = Arc::new(configuration());
+    let lens = OwnedLens::new(Arc::clone(&configuration));
+
+    let missing = lens.try_lens("clients").unwrap();
+    assert_eq!(3, Arc::strong_count(&configuration));
+    let port: Option<i32> = missing.try_lens("servers").unwrap().get("[0]:port");
+    assert_eq!(None, port);
+
+    // keys outside of window do not fall back to the window itself
+    let tail = lens.try_lens("servers:[1..]").unwrap();
+    let outside = tail.try_lens("[5]").unwrap();
+    let port: Option<i32> = outside.get("port");
+    assert_eq!(None, port);
+    let port: Option<i32> = outside.try_lens("[0]").unwrap().get("port");
+    assert_eq!(None, port);
+}
+
+#[test]
+fn test_owned_lens_into_missing_keys_is_empty() {
+    let lens = OwnedLens::from(configuration());
+
+    let missing = lens.try_lens("clients").unwrap();
+    let value: Option<String> = missing.get("host");
+    assert_eq!(None, value);
+    assert_eq!(0, missing.lens().len("").unwrap());
+
+    let error = lens.try_lens("servers:[0..1]:host").unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::MisplacedSlice(..)
+    ));
+}
@@ -0,0 +1,141 @@
+use miau::{
+    builder::ConfigurationBuilder,
+    configuration::{
+        CompoundKey, Configuration, ConfigurationInfo, ConfigurationRead, KeyMode, Snapshot,
+    },
+    error::ErrorCode,
+    format,
+    source::InMemorySource,
+};
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+fn configuration() -> Configuration {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "server" : { "host" : "localhost", "port" : 80 }, "ports" : [1, 2, 3] }"#,
+        ),
+        format::json(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "server" : { "port" : 8080 }, "ports" : [10] }"#),
+        format::json(),
+    );
+
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_snapshot_reads_same_values_as_configuration() {
+    let configuration = configuration();
+    let snapshot = configuration.snapshot().unwrap();
+
+    for key in &[
+        "server:host",
+        "server:port",
+        "ports:[0]",
+        "ports:[2]",
+        "ports:[-1]",
+    ] {
+        let expected: Option<String> = configuration.get(*key);
+        let actual: Option<String> = snapshot.get(*key);
+        assert_eq!(expected, actual, "key {}", key);
+    }
+
+    let missing: Option<i32> = snapshot.get("server:missing");
+    assert_eq!(None, missing);
+}
+
+#[test]
+fn test_snapshot_lookup_with_parsed_keys() {
+    let snapshot = Snapshot::try_from(configuration()).unwrap();
+
+    let port = CompoundKey::try_from("server:port").unwrap();
+    let last = CompoundKey::try_from("ports:[-1]").unwrap();
+
+    assert_eq!(Some(8080), snapshot.lookup(&port));
+    assert_eq!(Some(3), snapshot.lookup(&last));
+
+    let value: Option<i32> = snapshot.get(&port);
+    assert_eq!(Some(8080), value);
+}
+
+#[test]
+fn test_snapshot_does_not_observe_later_overrides() {
+    let mut configuration = configuration();
+    let snapshot = configuration.snapshot().unwrap();
+
+    let root = serde_json::from_str(r#"{ "server" : { "port" : 1 } }"#).unwrap();
+    configuration
+        .push_override(ConfigurationInfo::new("tests", "none"), root)
+        .unwrap();
+
+    let port: Option<i32> = configuration.get("server:port");
+    assert_eq!(Some(1), port);
+    assert_eq!(Some(8080), snapshot.get("server:port"));
+}
+
+#[test]
+fn test_snapshot_try_convert_into() {
+    let snapshot = configuration().snapshot().unwrap();
+
+    let server: Server = snapshot
+        .lens()
+        .try_lens("server")
+        .unwrap()
+        .try_convert_into()
+        .unwrap();
+    assert_eq!(
+        Server {
+            host: "localhost".into(),
+            port: 8080
+        },
+        server
+    );
+
+    let ports: Vec<i32> = snapshot.lens().get_deserialized("ports").unwrap().unwrap();
+    assert_eq!(vec![10, 2, 3], ports);
+}
+
+#[test]
+fn test_snapshot_respects_key_mode() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "Server" : { "Port" : 80 } }"#),
+        format::json(),
+    );
+    builder.key_mode(KeyMode::IgnoreCase);
+
+    let snapshot = builder.build().unwrap().snapshot().unwrap();
+    let key = CompoundKey::try_from("SERVER:PORT").unwrap();
+
+    assert_eq!(Some(80), snapshot.lookup(&key));
+    assert_eq!(Some(80), snapshot.get("server:port"));
+}
+
+#[test]
+fn test_snapshot_rejects_slices() {
+    let snapshot = configuration().snapshot().unwrap();
+
+    let result: Result<Option<i32>, _> = snapshot.get_result("ports:[1..]");
+    let error = result.unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::MisplacedSlice(..)
+    ));
+}
+
+#[test]
+fn test_snapshot_of_empty_configuration() {
+    let snapshot = Configuration::default().snapshot().unwrap();
+
+    let value: Option<i32> = snapshot.get("any");
+    assert_eq!(None, value);
+}
@@ -1,4 +1,10 @@
-use miau::{builder::ConfigurationBuilder, error::ErrorCode, format::Json, source::InMemorySource};
+use miau::{
+    builder::ConfigurationBuilder,
+    configuration::{ConfigurationRead, ConfigurationTree},
+    error::ErrorCode,
+    format::Json,
+    source::InMemorySource,
+};
 use rstest::rstest;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -32,14 +38,8 @@ use std::collections::HashMap;
 fn test_arrays_are_merged_when_substituted(json1: &str, json2: &str, exp: Vec<i32>) {
     let mut builder = ConfigurationBuilder::default();
 
-    builder.add(
-        InMemorySource::from_string_slice(json1.as_ref()),
-        Json::new(),
-    );
-    builder.add(
-        InMemorySource::from_string_slice(json2.as_ref()),
-        Json::new(),
-    );
+    builder.add(InMemorySource::from_string_slice(json1), Json::new());
+    builder.add(InMemorySource::from_string_slice(json2), Json::new());
 
     let confiuration = builder.build().unwrap();
 
@@ -47,7 +47,7 @@ fn test_arrays_are_merged_when_substituted(json1: &str, json2: &str, exp: Vec<i3
         .try_convert_into::<HashMap<String, Vec<i32>>>()
         .unwrap();
 
-    assert_eq!(exp, result.remove("array1".into()).unwrap());
+    assert_eq!(exp, result.remove("array1").unwrap());
 }
 
 #[test]
@@ -169,12 +169,81 @@ fn test_node_merge_error_messages(cfg1: &str, cfg2: &str, exp_key: &str, exp_for
 
     let confiuration = builder.build().unwrap();
 
+    let borrowed_error = confiuration.lens().merge_cloned().unwrap_err();
     let error = confiuration.merge_owned().unwrap_err();
 
     assert!(std::matches!(error.get_code(), ErrorCode::BadNodeMerge(..)));
     let error_string = error.to_string();
     assert!(error_string.contains(exp_key));
     assert!(error_string.contains(exp_for));
+    assert_eq!(error_string, borrowed_error.to_string());
+}
+
+#[test]
+fn test_borrowed_and_owned_merges_are_equal() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "db" : { "host" : "a", "ports" : [1, 2, 3], "pool" : { "min" : 1, "max" : 2 } }, "name" : "first", "tags" : ["x"] }"#,
+        ),
+        Json::new(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "db" : { "ports" : [10], "pool" : { "max" : 20 } }, "name" : null, "tags" : ["y", "z"] }"#,
+        ),
+        Json::new(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "db" : { "host" : "c", "extra" : { "nested" : [ { "key" : 1 } ] } }, "name" : "third" }"#,
+        ),
+        Json::new(),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    let borrowed = configuration.lens().merge_cloned().unwrap();
+    let lensed = configuration
+        .lens()
+        .try_lens("db")
+        .unwrap()
+        .merge_cloned()
+        .unwrap();
+    let owned = configuration.merge_owned().unwrap();
+
+    assert_eq!(owned, borrowed);
+    match owned {
+        ConfigurationTree::Map(map) => assert_eq!(Some(&lensed), map.get("db")),
+        other => panic!("expected map, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_value_overrides_incompatible_root() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "key" : 1 }"#),
+        Json::new(),
+    );
+    builder.add(InMemorySource::from_string_slice("[1, 2]"), Json::new());
+    builder.add(InMemorySource::from_string_slice("3"), Json::new());
+
+    let configuration = builder.build().unwrap();
+
+    assert!(configuration.lens().merge_cloned().is_err());
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "key" : 1 }"#),
+        Json::new(),
+    );
+    builder.add(InMemorySource::from_string_slice("3"), Json::new());
+
+    let configuration = builder.build().unwrap();
+    let value: i32 = configuration.lens().try_convert_into().unwrap();
+
+    assert_eq!(3, value);
 }
 
 #[test]
@@ -193,3 +262,79 @@ fn test_merge_empty_configuration_is_error() {
     assert!(error.get_context().is_some());
     assert!(!error.get_context().unwrap().is_empty())
 }
+
+// records address of borrowed strings, which stays the same only if they are not cloned
+#[derive(Debug)]
+struct Address(Option<usize>);
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AddressVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for AddressVisitor {
+            type Value = Address;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "string")
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(Address(Some(v.as_ptr() as usize)))
+            }
+
+            fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+                Ok(Address(None))
+            }
+        }
+
+        deserializer.deserialize_str(AddressVisitor)
+    }
+}
+
+#[test]
+fn test_subtrees_are_not_cloned_when_merged() {
+    #[derive(Deserialize)]
+    struct Config {
+        server: Server,
+        db: Db,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Server {
+        host: Address,
+        port: i32,
+    }
+
+    #[derive(Deserialize)]
+    struct Db {
+        name: Address,
+    }
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "server" : { "host" : "localhost", "port" : 80 }, "db" : { "name" : "users" } }"#,
+        ),
+        Json::new(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "server" : { "port" : 8080 } }"#),
+        Json::new(),
+    );
+
+    let configuration = builder.build().unwrap();
+    let host: &str = configuration.get("server:host").unwrap();
+    let host = host.as_ptr() as usize;
+    let name: &str = configuration.get("db:name").unwrap();
+    let name = name.as_ptr() as usize;
+
+    let config: Config = configuration.lens().try_convert_into().unwrap();
+    assert_eq!(Some(host), config.server.host.0);
+    assert_eq!(Some(name), config.db.name.0);
+
+    // strings are stored on the heap, so moving configuration does not change their addresses
+    let config: Config = configuration.try_convert_into().unwrap();
+    assert_eq!(Some(host), config.server.host.0);
+    assert_eq!(Some(name), config.db.name.0);
+}
@@ -1,8 +1,8 @@
 use miau::{
     builder::ConfigurationBuilder,
-    configuration::{Configuration, ConfigurationTree},
+    configuration::{Configuration, ConfigurationTree, Key},
     error::ErrorCode,
-    format::Json,
+    format::{self, Json},
     source::InMemorySource,
 };
 use serde::Deserialize;
@@ -58,7 +58,7 @@ fn test_deserialization_all_simple_types() {
     assert_eq!(31, config.uinteger32);
     assert_eq!(15, config.uinteger16);
     assert_eq!(7, config.uinteger8);
-    assert_eq!(true, config.boolean);
+    assert!(config.boolean);
     assert_eq!("owned".to_string(), config.string_owned);
     assert_eq!(1.1, config.float32);
     assert_eq!(1.2, config.float64);
@@ -69,6 +69,7 @@ fn test_deserialization_all_simple_types() {
 #[test]
 fn test_error_when_deserializing_internal_struct_fails() {
     #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
     struct Config {
         some_integer_field: u32,
     }
@@ -87,19 +88,20 @@ fn test_error_when_deserializing_internal_struct_fails() {
     ));
     let error_stringified = error.to_string();
     assert!(error_stringified.contains("some_integer_field"));
-    assert!(error_stringified.contains(&format!("{}", std::any::type_name::<Config>())));
+    assert!(error_stringified.contains(&std::any::type_name::<Config>().to_string()));
 }
 
 #[test]
 fn test_error_when_deserializing_char_longer_than_one() {
     #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
     struct Config {
         character: char,
-    };
+    }
 
     let json = r#"{ "character" : "longer" }"#;
 
-    let root = serde_json::from_str::<ConfigurationTree>(&json).unwrap();
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
 
     let error = root.try_convert_into::<Config>().unwrap_err();
 
@@ -167,7 +169,7 @@ fn test_deserialization_struct_with_array() {
 
     let config = root.try_convert_into::<Config>().unwrap();
 
-    assert!(vec![1, 2, 3].iter().eq(config.inner.iter()));
+    assert!([1, 2, 3].iter().eq(config.inner.iter()));
 }
 
 #[test]
@@ -195,7 +197,7 @@ fn test_deserialization_struct_with_array_of_structs() {
 
     let config = root.try_convert_into::<Config>().unwrap();
 
-    assert!(vec![
+    assert!([
         ConfigInner { value: 1 },
         ConfigInner { value: 2 },
         ConfigInner { value: 3 }
@@ -228,7 +230,7 @@ fn test_deserialization_struct_with_array_of_structs_transparent() {
 
     let config = root.try_convert_into::<Config>().unwrap();
 
-    assert!(vec![
+    assert!([
         ConfigInner { value: 1 },
         ConfigInner { value: 2 },
         ConfigInner { value: 3 }
@@ -487,3 +489,545 @@ fn test_deserialization_option() {
     assert_eq!(None, config.none);
     assert_eq!(None, config.none2);
 }
+
+#[test]
+fn test_error_path_points_to_nested_array_element() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Server {
+        port: u16,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        servers: Vec<Server>,
+    }
+
+    let json = r#"{ "servers" : [ { "port" : 80 }, { "port" : 81 }, { "port" : "http" } ] }"#;
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
+
+    let error = root.try_convert_into::<Config>().unwrap_err();
+
+    assert_eq!(
+        &[
+            Key::Map("port".into()),
+            Key::Array(2),
+            Key::Map("servers".into())
+        ],
+        error.get_path().unwrap()
+    );
+    assert!(error.to_string().contains("servers-->[2]-->port"));
+}
+
+#[test]
+fn test_error_path_points_to_struct_missing_field() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Database {
+        user: String,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        database: Database,
+    }
+
+    let json = r#"{ "database" : { "name" : "db" } }"#;
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
+
+    let error = root.try_convert_into::<Config>().unwrap_err();
+
+    assert!(error.to_string().contains("missing field `user`"));
+    assert_eq!(&[Key::Map("database".into())], error.get_path().unwrap());
+}
+
+#[test]
+fn test_error_path_points_into_hashmap_values() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        limits: HashMap<String, Vec<u32>>,
+    }
+
+    let json = r#"{ "limits" : { "cpu" : [1, 2], "memory" : [1, "lots"] } }"#;
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
+
+    let error = root.try_convert_into::<Config>().unwrap_err();
+
+    assert!(error.to_string().contains("limits-->memory-->[1]"));
+}
+
+#[test]
+fn test_error_path_points_into_enum_variant() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    enum Storage {
+        Disk { path: String, size: u32 },
+        Memory(u32),
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        storage: Vec<Storage>,
+    }
+
+    let json =
+        r#"{ "storage" : [ { "Memory" : 5 }, { "Disk" : { "path" : "/tmp", "size" : "big" } } ] }"#;
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
+
+    let error = root.try_convert_into::<Config>().unwrap_err();
+
+    assert!(error.to_string().contains("storage-->[1]-->Disk-->size"));
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct ReportedServer {
+    host: String,
+    port: u16,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct ReportedConfig {
+    name: String,
+    debug: bool,
+    servers: Vec<ReportedServer>,
+    timeout: Option<u32>,
+}
+
+fn error_paths(error: &miau::error::ConfigurationError) -> Vec<String> {
+    let errors = match error.get_code() {
+        ErrorCode::MultipleErrors(errors) => errors.iter().collect(),
+        _ => vec![error],
+    };
+
+    let mut paths: Vec<String> = errors
+        .iter()
+        .map(|e| {
+            e.get_path()
+                .unwrap()
+                .iter()
+                .rev()
+                .map(|k| k.to_string())
+                .collect::<Vec<_>>()
+                .join("-->")
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn test_reporting_all_errors_succeeds_for_valid_configuration() {
+    let json =
+        r#"{ "name" : "app", "debug" : true, "servers" : [ { "host" : "a", "port" : 1 } ] }"#;
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
+
+    let config = root
+        .try_convert_into_reporting_all::<ReportedConfig>()
+        .unwrap();
+
+    assert_eq!(
+        ReportedConfig {
+            name: "app".into(),
+            debug: true,
+            servers: vec![ReportedServer {
+                host: "a".into(),
+                port: 1
+            }],
+            timeout: None,
+        },
+        config
+    );
+}
+
+#[test]
+fn test_reporting_all_errors_of_sibling_and_nested_fields() {
+    let json = r#"{
+        "debug" : "sometimes",
+        "servers" : [
+            { "host" : "a", "port" : 1 },
+            { "host" : "b", "port" : "http" },
+            { "port" : [] }
+        ],
+        "timeout" : "never"
+    }"#;
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
+
+    let error = root
+        .try_convert_into_reporting_all::<ReportedConfig>()
+        .unwrap_err();
+
+    assert_eq!(
+        vec![
+            "debug",
+            "name",
+            "servers-->[1]-->port",
+            "servers-->[2]-->host",
+            "servers-->[2]-->port",
+            "timeout",
+        ],
+        error_paths(&error)
+    );
+    assert!(error
+        .to_string()
+        .contains(&std::any::type_name::<ReportedConfig>().to_string()));
+
+    let pretty = error.pretty_display().to_string();
+    assert!(pretty.starts_with("6 errors occurred."));
+    assert!(pretty.contains("missing field `name`"));
+}
+
+#[test]
+fn test_reporting_all_errors_returns_single_error_directly() {
+    let json = r#"{ "name" : "app", "debug" : 5, "servers" : [] }"#;
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
+
+    let error = root
+        .try_convert_into_reporting_all::<ReportedConfig>()
+        .unwrap_err();
+
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::WrongValueType(..) | ErrorCode::DeserializationError(..)
+    ));
+    assert_eq!(vec!["debug"], error_paths(&error));
+}
+
+#[test]
+fn test_reporting_all_errors_inside_enum_variants() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    enum Storage {
+        Disk { path: String, size: u32 },
+        Memory(u32),
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        storage: Vec<Storage>,
+    }
+
+    let json = r#"{ "storage" : [ { "Memory" : "lots" }, { "Disk" : { "size" : "big" } }, { "Tape" : 1 } ] }"#;
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
+
+    let error = root.try_convert_into_reporting_all::<Config>().unwrap_err();
+
+    assert_eq!(
+        vec![
+            "storage-->[0]-->Memory",
+            "storage-->[1]-->Disk-->path",
+            "storage-->[1]-->Disk-->size",
+            "storage-->[2]",
+        ],
+        error_paths(&error)
+    );
+}
+
+#[test]
+fn test_reporting_all_errors_of_wrong_types_in_single_pass() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Counted(ReportedConfig);
+
+    impl<'de> Deserialize<'de> for Counted {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+            ReportedConfig::deserialize(deserializer).map(Counted)
+        }
+    }
+
+    let json = r#"{
+        "name" : [],
+        "debug" : "sometimes",
+        "servers" : [ { "host" : {}, "port" : "http" }, "server" ],
+        "timeout" : "never"
+    }"#;
+    let root = serde_json::from_str::<ConfigurationTree>(json).unwrap();
+
+    let error = root
+        .try_convert_into_reporting_all::<Counted>()
+        .unwrap_err();
+
+    assert_eq!(
+        vec![
+            "debug",
+            "name",
+            "servers-->[0]-->host",
+            "servers-->[0]-->port",
+            "servers-->[1]",
+            "timeout",
+        ],
+        error_paths(&error)
+    );
+    assert_eq!(1, ATTEMPTS.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_reporting_all_errors_names_configuration_defining_value() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "name" : "app", "debug" : "yes", "servers" : [] }"#),
+        format::json(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "debug" : "no", "timeout" : "never" }"#),
+        format::yaml(),
+    );
+
+    let configuration = builder.build().unwrap();
+    let error = configuration
+        .try_convert_into_reporting_all::<ReportedConfig>()
+        .unwrap_err();
+
+    let errors = match error.get_code() {
+        ErrorCode::MultipleErrors(errors) => errors,
+        _ => panic!("expected multiple errors"),
+    };
+    assert_eq!(2, errors.len());
+
+    for e in errors {
+        let context = e.get_context().unwrap();
+        assert!(context
+            .iter()
+            .any(|c| c.starts_with("Value defined in : ") && c.contains("format : yaml")));
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Deserialize, Debug)]
+struct UnknownKeysConfig {
+    name: String,
+    servers: Vec<Server>,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+
+fn unknown_keys(errors: &[miau::error::ConfigurationError]) -> Vec<String> {
+    errors
+        .iter()
+        .map(|e| match e.get_code() {
+            ErrorCode::UnknownKey(key) => {
+                let path = e.get_path().unwrap();
+                assert_eq!(&Key::Map(key.clone()), path.first().unwrap());
+                path.iter()
+                    .rev()
+                    .map(|k| k.to_string())
+                    .collect::<Vec<String>>()
+                    .join("-->")
+            }
+            _ => panic!("expected unknown key, got {}", e),
+        })
+        .collect()
+}
+
+#[test]
+fn test_reporting_unknown_keys_with_their_paths() {
+    let tree: ConfigurationTree = serde_json::from_str(
+        r#"{
+            "name" : "app",
+            "nmae" : "typo",
+            "servers" : [
+                { "host" : "a", "port" : 80 },
+                { "host" : "b", "port" : 81, "tls" : { "enabled" : true } }
+            ],
+            "labels" : { "anything" : "goes" }
+        }"#,
+    )
+    .unwrap();
+
+    let (config, warnings) = tree
+        .try_convert_into_reporting_unknown::<UnknownKeysConfig>()
+        .unwrap();
+
+    assert_eq!("app", config.name);
+    assert_eq!(2, config.servers.len());
+    assert_eq!(
+        ("b", 81),
+        (config.servers[1].host.as_str(), config.servers[1].port)
+    );
+    assert_eq!("goes", config.labels["anything"]);
+
+    let mut keys = unknown_keys(&warnings);
+    keys.sort();
+    assert_eq!(vec!["nmae", "servers-->[1]-->tls"], keys);
+}
+
+#[test]
+fn test_reporting_unknown_keys_returns_no_warnings_for_exact_match() {
+    let tree: ConfigurationTree = serde_json::from_str(
+        r#"{ "name" : "app", "servers" : [ { "host" : "a", "port" : 80 } ] }"#,
+    )
+    .unwrap();
+
+    let (_, warnings) = tree
+        .try_convert_into_reporting_unknown::<UnknownKeysConfig>()
+        .unwrap();
+
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_reporting_unknown_keys_still_fails_on_invalid_values() {
+    let tree: ConfigurationTree =
+        serde_json::from_str(r#"{ "name" : "app", "servers" : 3, "extra" : 1 }"#).unwrap();
+
+    let error = tree
+        .try_convert_into_reporting_unknown::<UnknownKeysConfig>()
+        .unwrap_err();
+
+    assert!(!std::matches!(error.get_code(), ErrorCode::UnknownKey(..)));
+    assert_eq!(vec!["servers"], error_paths(&error));
+}
+
+#[test]
+fn test_denying_unknown_keys() {
+    let tree: ConfigurationTree = serde_json::from_str(
+        r#"{ "name" : "app", "servers" : [ { "host" : "a", "port" : 80, "prot" : 1 } ], "debug" : true }"#,
+    )
+    .unwrap();
+
+    let error = tree
+        .try_convert_into_denying_unknown::<UnknownKeysConfig>()
+        .unwrap_err();
+
+    let errors = match error.get_code() {
+        ErrorCode::MultipleErrors(errors) => errors,
+        _ => panic!("expected multiple errors"),
+    };
+    let mut keys = unknown_keys(errors);
+    keys.sort();
+    assert_eq!(vec!["debug", "servers-->[0]-->prot"], keys);
+
+    let tree: ConfigurationTree =
+        serde_json::from_str(r#"{ "name" : "app", "servers" : [] }"#).unwrap();
+    assert!(tree
+        .try_convert_into_denying_unknown::<UnknownKeysConfig>()
+        .is_ok());
+}
+
+#[test]
+fn test_reporting_unknown_keys_names_configuration_defining_them() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "name" : "app", "servers" : [] }"#),
+        format::json(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice("name: other\nverbose: true\n"),
+        format::yaml(),
+    );
+    builder.source_maps(true);
+
+    let configuration = builder.build().unwrap();
+    let (config, warnings) = configuration
+        .try_convert_into_reporting_unknown::<UnknownKeysConfig>()
+        .unwrap();
+
+    assert_eq!("other", config.name);
+    assert_eq!(vec!["verbose"], unknown_keys(&warnings));
+
+    let context = warnings[0].get_context().unwrap();
+    assert!(context
+        .iter()
+        .any(|c| c.starts_with("Value defined in : ") && c.contains("format : yaml")));
+    assert_eq!(2, warnings[0].get_location().unwrap().line());
+}
+
+#[test]
+fn test_get_deserialized_merges_subtrees_of_all_layers() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "app" : { "hosts" : ["a", "b", "c"], "limits" : { "cpu" : 2, "memory" : 512 } } }"#,
+        ),
+        format::json(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{ "app" : { "hosts" : ["x"], "limits" : { "cpu" : 4 } } }"#,
+        ),
+        format::json(),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    let hosts: Vec<String> = configuration
+        .get_deserialized("app:hosts")
+        .unwrap()
+        .unwrap();
+    assert_eq!(vec!["x", "b", "c"], hosts);
+
+    let limits: HashMap<String, u32> = configuration
+        .get_deserialized("app:limits")
+        .unwrap()
+        .unwrap();
+    assert_eq!(4, limits["cpu"]);
+    assert_eq!(512, limits["memory"]);
+
+    let lens = configuration.lens().try_lens("app").unwrap();
+    let cpu: u32 = lens.get_deserialized("limits:cpu").unwrap().unwrap();
+    assert_eq!(4, cpu);
+
+    let missing: Option<Vec<String>> = configuration.get_deserialized("app:ports").unwrap();
+    assert!(missing.is_none());
+}
+
+#[test]
+fn test_get_deserialized_errors_count_from_root() {
+    #[derive(Deserialize, Debug)]
+    struct Limits {
+        #[allow(dead_code)]
+        cpu: u32,
+    }
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "app" : { "limits" : [ { "cpu" : "many" } ] } }"#),
+        format::json(),
+    );
+
+    let configuration = builder.build().unwrap();
+    let error = configuration
+        .get_deserialized::<Vec<Limits>, _>("app:limits")
+        .unwrap_err();
+
+    assert_eq!(vec!["app-->limits-->[0]-->cpu"], error_paths(&error));
+    assert!(error.to_string().contains("Value defined in"));
+
+    let error = configuration
+        .get_deserialized::<Vec<Limits>, _>("app:[A]")
+        .unwrap_err();
+    assert!(std::matches!(error.get_code(), ErrorCode::ParsingError(..)));
+}
+
+#[test]
+fn test_get_deserialized_from_tree() {
+    let tree: ConfigurationTree = serde_json::from_str(
+        r#"{ "servers" : [ { "host" : "a", "port" : 80 }, { "host" : "b", "port" : "x" } ] }"#,
+    )
+    .unwrap();
+
+    let server: Server = tree.get_deserialized("servers:[0]").unwrap().unwrap();
+    assert_eq!(("a", 80), (server.host.as_str(), server.port));
+
+    let missing: Option<Server> = tree.get_deserialized("servers:[2]").unwrap();
+    assert!(missing.is_none());
+
+    let error = tree
+        .get_deserialized::<Vec<Server>, _>("servers")
+        .unwrap_err();
+    assert_eq!(vec!["servers-->[1]-->port"], error_paths(&error));
+}
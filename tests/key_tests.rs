@@ -1,4 +1,5 @@
-use miau::configuration::Key;
+use miau::configuration::{CompoundKey, Key};
+use std::convert::TryInto;
 
 #[test]
 fn test_key_succesfull_unwrap() {
@@ -24,3 +25,37 @@ fn test_key_array_unwrap_into_map_panics() {
 
     let _ = key_arr.unwrap_map();
 }
+
+#[test]
+fn test_compound_key_display_round_trips() {
+    for input in &["", "key", "[1]", "key:[1]:key2", "[1]:key:[2]"] {
+        let key: CompoundKey = (*input).try_into().unwrap();
+        assert_eq!(*input, key.to_string());
+
+        let parsed: CompoundKey = key.to_string().try_into().unwrap();
+        assert_eq!(key, parsed);
+    }
+}
+
+#[test]
+fn test_compound_key_display_quotes_special_keys() {
+    let key = CompoundKey::new(vec![
+        Key::Map("hosts".into()),
+        Key::Map("::1".into()),
+        Key::Map("".into()),
+        Key::Map(r#"say "hi" \ bye"#.into()),
+        Key::Map(" padded".into()),
+        Key::Map("[0]".into()),
+        Key::Array(0),
+        Key::Map("two words".into()),
+    ]);
+
+    let displayed = key.to_string();
+    assert_eq!(
+        r#"hosts:"::1":"":"say \"hi\" \\ bye":" padded":"[0]":[0]:two words"#,
+        displayed
+    );
+
+    let parsed: CompoundKey = displayed.try_into().unwrap();
+    assert_eq!(key, parsed);
+}
@@ -0,0 +1,173 @@
+use miau::{
+    builder::ConfigurationBuilder,
+    configuration::{ConfigurationRead, ConfigurationTree, Value},
+    error::ErrorCode,
+    format::Json,
+    provider::SerializeProvider,
+    source::InMemorySource,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Mode {
+    Fast,
+    Slow,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct AppConfig {
+    name: String,
+    verbose: bool,
+    ratio: f64,
+    retries: u32,
+    mode: Mode,
+    optional: Option<String>,
+    servers: Vec<Server>,
+    labels: HashMap<String, String>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        let mut labels = HashMap::new();
+        labels.insert("team".into(), "core".into());
+
+        AppConfig {
+            name: "app".into(),
+            verbose: false,
+            ratio: 0.5,
+            retries: 3,
+            mode: Mode::Fast,
+            optional: None,
+            servers: vec![
+                Server {
+                    host: "localhost".into(),
+                    port: 8080,
+                },
+                Server {
+                    host: "remote".into(),
+                    port: 9090,
+                },
+            ],
+            labels,
+        }
+    }
+}
+
+#[test]
+fn test_serialization_all_simple_types() {
+    let tree = ConfigurationTree::from_serialize(&AppConfig::default()).unwrap();
+
+    assert_eq!(Some("app"), tree.get("name"));
+    assert_eq!(Some(false), tree.get("verbose"));
+    assert_eq!(Some(0.5), tree.get("ratio"));
+    assert_eq!(Some(3), tree.get("retries"));
+    assert_eq!(Some("Fast"), tree.get("mode"));
+    assert_eq!(
+        None,
+        ConfigurationRead::<'_, String, &str>::get(&tree, "optional")
+    );
+    assert_eq!(Some("remote"), tree.get("servers:[1]:host"));
+    assert_eq!(Some(8080), tree.get("servers:[0]:port"));
+    assert_eq!(Some("core"), tree.get("labels:team"));
+}
+
+#[test]
+fn test_serialization_round_trip() {
+    let tree = ConfigurationTree::from_serialize(&AppConfig::default()).unwrap();
+
+    let config = tree.try_convert_into::<AppConfig>().unwrap();
+
+    assert_eq!(AppConfig::default(), config);
+}
+
+#[test]
+fn test_serialization_of_enum_variants() {
+    #[derive(Serialize)]
+    enum Complex {
+        Newtype(i32),
+        Tuple(i32, i32),
+        Struct { field: bool },
+    }
+
+    let tree = ConfigurationTree::from_serialize(&vec![
+        Complex::Newtype(1),
+        Complex::Tuple(2, 3),
+        Complex::Struct { field: true },
+    ])
+    .unwrap();
+
+    assert_eq!(Some(1), tree.get("[0]:Newtype"));
+    assert_eq!(Some(3), tree.get("[1]:Tuple:[1]"));
+    assert_eq!(Some(true), tree.get("[2]:Struct:field"));
+}
+
+#[test]
+fn test_serialization_of_non_string_map_keys() {
+    let mut map = HashMap::new();
+    map.insert(1u8, "one");
+    map.insert(2u8, "two");
+
+    let tree = ConfigurationTree::from_serialize(&map).unwrap();
+
+    assert_eq!(Some("one"), tree.get("1"));
+    assert_eq!(Some("two"), tree.get("2"));
+}
+
+#[test]
+fn test_serialization_of_plain_value() {
+    let tree = ConfigurationTree::from_serialize(&42).unwrap();
+
+    assert_eq!(
+        ConfigurationTree::Value(Some(Value::SignedInteger(42))),
+        tree
+    );
+}
+
+#[test]
+fn test_serialization_error_contains_path() {
+    #[derive(Serialize)]
+    struct Config {
+        inner: Vec<u64>,
+    }
+
+    let error = ConfigurationTree::from_serialize(&Config {
+        inner: vec![1, u64::MAX],
+    })
+    .unwrap_err();
+
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::SerializationError(..)
+    ));
+    let error_stringified = error.to_string();
+    assert!(error_stringified.contains("inner-->[1]"));
+}
+
+#[test]
+fn test_serialize_provider_is_lowest_layer() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(SerializeProvider::new(AppConfig::default()));
+    builder.add(
+        InMemorySource::from_string_slice(
+            r#"{"verbose" : true, "servers" : [{"host" : "other", "port" : 1}]}"#,
+        ),
+        Json::new(),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some(true), configuration.get("verbose"));
+    assert_eq!(Some("app"), configuration.get("name"));
+    assert_eq!(Some(1), configuration.get("servers:[0]:port"));
+
+    let config = configuration.try_convert_into::<AppConfig>().unwrap();
+    assert!(config.verbose);
+    assert_eq!(2, config.servers.len());
+}
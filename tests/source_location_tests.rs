@@ -0,0 +1,301 @@
+use miau::{
+    builder::ConfigurationBuilder,
+    configuration::{Configuration, ConfigurationRead, Key, SourceMap},
+    error::{ConfigurationError, ErrorCode},
+    format::{self, Format},
+    source::{FileSource, InMemorySource},
+};
+use rstest::rstest;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct Database {
+    user: String,
+    password: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct Config {
+    name: String,
+    servers: Vec<Server>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct DatabaseConfig {
+    database: Database,
+}
+
+fn build<F: Format + 'static>(file: &str, format: F) -> Configuration {
+    let mut builder = ConfigurationBuilder::default();
+    builder.source_maps(true);
+    builder.add(
+        FileSource::from_path(format!("./tests/files/located/{}", file)),
+        format,
+    );
+    builder.build().unwrap()
+}
+
+fn formats() -> Vec<(&'static str, Box<dyn Format + Send + Sync>)> {
+    vec![
+        ("config.json", Box::new(format::json())),
+        ("config.yaml", Box::new(format::yaml())),
+        ("config.toml", Box::new(format::toml())),
+    ]
+}
+
+#[rstest(
+    file,
+    line,
+    column,
+    excerpt,
+    case("config.json", 8, 20, r#"    { "host": "b", "port": "http" }"#),
+    case("config.yaml", 8, 5, "    port: http"),
+    case("config.toml", 13, 1, r#"port = "http""#)
+)]
+fn test_conversion_error_points_at_source_location(
+    file: &str,
+    line: usize,
+    column: usize,
+    excerpt: &str,
+) {
+    let (_, format) = formats().into_iter().find(|(f, _)| *f == file).unwrap();
+    let configuration = build(file, format);
+
+    let error = configuration.try_convert_into::<Config>().unwrap_err();
+
+    let location = error.get_location().unwrap();
+    assert!(location.source().ends_with(file));
+    assert_eq!(line, location.line());
+    assert_eq!(column, location.column());
+    assert_eq!(Some(excerpt), location.excerpt());
+
+    assert!(error
+        .to_string()
+        .contains(&format!("{}:{}:{}", file, line, column)));
+
+    let pretty = error.pretty_display().to_string();
+    assert!(pretty.contains(&format!("\t{} | {}\n", line, excerpt)));
+    assert!(pretty.contains(&format!(
+        "\t{} | {}^\n",
+        " ".repeat(line.to_string().len()),
+        " ".repeat(column - 1)
+    )));
+}
+
+#[rstest(
+    file,
+    line,
+    case("config.json", 3),
+    case("config.yaml", 2),
+    case("config.toml", 4)
+)]
+fn test_missing_field_points_at_parent(file: &str, line: usize) {
+    let (_, format) = formats().into_iter().find(|(f, _)| *f == file).unwrap();
+    let configuration = build(file, format);
+
+    let error = configuration
+        .try_convert_into::<DatabaseConfig>()
+        .unwrap_err();
+
+    assert!(error.to_string().contains("missing field `password`"));
+    assert_eq!(line, error.get_location().unwrap().line());
+}
+
+#[test]
+fn test_locate_values_through_lens() {
+    for (file, format) in formats() {
+        let configuration = build(file, format);
+
+        let lens = configuration.lens().try_lens("servers:[1]").unwrap();
+        let host = lens.locate("host").unwrap().unwrap();
+        let root_host = configuration.locate("servers:[1]:host").unwrap().unwrap();
+
+        assert_eq!(root_host, host, "{}", file);
+        assert!(host.excerpt().unwrap().contains("host"), "{}", file);
+        assert!(host.excerpt().unwrap().contains('b'), "{}", file);
+        assert_eq!(
+            None,
+            configuration.locate("servers:[7]").unwrap(),
+            "{}",
+            file
+        );
+    }
+}
+
+#[test]
+fn test_location_comes_from_configuration_defining_value() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.source_maps(true);
+    builder.add(
+        FileSource::from_path("./tests/files/located/config.json"),
+        format::json(),
+    );
+    builder.add(
+        InMemorySource::from_string_slice("name = \"other\"\n\n[servers]\nport = \"ssh\"\n"),
+        format::toml(),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    let name = configuration.locate("name").unwrap().unwrap();
+    assert_eq!("inmemory", name.source());
+    assert_eq!(1, name.line());
+
+    let host = configuration.locate("servers:[0]:host").unwrap().unwrap();
+    assert!(host.source().ends_with("config.json"));
+}
+
+#[test]
+fn test_configuration_without_source_map_has_no_location() {
+    let mut overrides = HashMap::new();
+    overrides.insert("name".to_string(), "other".to_string());
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.source_maps(true);
+    builder.add(
+        FileSource::from_path("./tests/files/located/config.json"),
+        format::json(),
+    );
+    builder.add_provider(Configuration::from(overrides));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some("other"), configuration.get("name"));
+    assert_eq!(None, configuration.locate("name").unwrap());
+    assert!(configuration.locate("servers").unwrap().is_some());
+}
+
+#[test]
+fn test_source_maps_are_disabled_by_default() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        FileSource::from_path("./tests/files/located/config.json"),
+        format::json(),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(None, configuration.locate("name").unwrap());
+    let error = configuration
+        .try_convert_into::<DatabaseConfig>()
+        .unwrap_err();
+    assert_eq!(None, error.get_location());
+}
+
+#[test]
+fn test_reporting_all_errors_points_at_source_locations() {
+    let configuration = build("config.yaml", format::yaml());
+
+    let error = configuration
+        .try_convert_into_reporting_all::<DatabaseConfig>()
+        .unwrap_err();
+
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::DeserializationError(..)
+    ));
+    assert_eq!(2, error.get_location().unwrap().line());
+}
+
+#[test]
+fn test_source_map_of_json_with_escapes() {
+    let json = "{\"a\\\"b\" : [1, {\"c\\u0041\" : \"x\\\\\"}],\n \"d\" : null}";
+
+    let source_map: SourceMap = format::json().source_map(json.as_bytes()).unwrap();
+
+    let position = |path: Vec<Key>| {
+        let p = source_map.get(&path).unwrap();
+        (p.line, p.column)
+    };
+    assert_eq!((1, 2), position(vec!["a\"b".into()]));
+    assert_eq!((1, 12), position(vec!["a\"b".into(), 0usize.into()]));
+    assert_eq!((1, 15), position(vec!["a\"b".into(), 1usize.into()]));
+    assert_eq!(
+        (1, 16),
+        position(vec!["a\"b".into(), 1usize.into(), "cA".into()])
+    );
+    assert_eq!((2, 2), position(vec!["d".into()]));
+}
+
+#[test]
+fn test_source_map_of_toml_with_dotted_keys_and_multiline_values() {
+    let toml = r#"
+a.b = 1
+"quoted.key" = 2
+list = [
+  "c = 3",
+]
+text = """
+d = 4
+"""
+
+[e.f]
+g = 5
+"#;
+
+    let source_map = format::toml().source_map(toml.as_bytes()).unwrap();
+
+    let line = |path: Vec<Key>| source_map.get(&path).map(|p| p.line);
+    assert_eq!(Some(2), line(vec!["a".into()]));
+    assert_eq!(Some(2), line(vec!["a".into(), "b".into()]));
+    assert_eq!(Some(3), line(vec!["quoted.key".into()]));
+    assert_eq!(Some(4), line(vec!["list".into()]));
+    assert_eq!(None, line(vec!["c".into()]));
+    assert_eq!(None, line(vec!["d".into()]));
+    assert_eq!(Some(11), line(vec!["e".into()]));
+    assert_eq!(Some(12), line(vec!["e".into(), "f".into(), "g".into()]));
+}
+
+#[test]
+fn test_source_map_of_toml_with_escapes() {
+    let toml = r#"
+"a\"b" = "x\" = [ # not a comment"
+c = "\\" # [ comment
+"d\u0041" = """
+e = \""" ]
+"""
+list = [ '\', "[" ]
+f = 1
+
+["g\"]".h]
+i = 2
+"#;
+
+    assert!(format::toml().transform(toml.as_bytes().to_vec()).is_ok());
+    let source_map = format::toml().source_map(toml.as_bytes()).unwrap();
+
+    let line = |path: Vec<Key>| source_map.get(&path).map(|p| p.line);
+    assert_eq!(Some(2), line(vec!["a\"b".into()]));
+    assert_eq!(Some(3), line(vec!["c".into()]));
+    assert_eq!(Some(4), line(vec!["dA".into()]));
+    assert_eq!(None, line(vec!["e".into()]));
+    assert_eq!(Some(7), line(vec!["list".into()]));
+    assert_eq!(Some(8), line(vec!["f".into()]));
+    assert_eq!(Some(10), line(vec!["g\"]".into(), "h".into()]));
+    assert_eq!(Some(11), line(vec!["g\"]".into(), "h".into(), "i".into()]));
+}
+
+#[test]
+fn test_source_map_does_not_fail_on_malformed_input() {
+    let inputs = ["{\"a\" : [1, 2", "a:\n  - b: [", "[a\nb = \"c"];
+
+    for input in inputs.iter() {
+        let _ = format::json().source_map(input.as_bytes());
+        let _ = format::yaml().source_map(input.as_bytes());
+        let _ = format::toml().source_map(input.as_bytes());
+    }
+
+    let error: ConfigurationError = ErrorCode::NullValue.into();
+    assert!(error.get_location().is_none());
+}
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use miau::{
+    builder::{AsyncConfigurationBuilder, ConfigurationBuilder},
+    configuration::ConfigurationRead,
+    error::{ConfigurationError, ErrorCode},
+    format,
+    provider::Cached,
+    source::{AsyncSource, Source},
+};
+use std::{
+    env, fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+fn cache_path(name: &str) -> PathBuf {
+    let path = env::temp_dir()
+        .join(format!("miau-cache-{}", std::process::id()))
+        .join(name);
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[tokio::test]
+async fn test_cached_serves_last_known_good_content() {
+    let path = cache_path("async.json");
+    let content = Arc::new(Mutex::new(Some(r#"{ "value" : 1 }"#.to_string())));
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_provider_async(Cached::new(
+        SwitchableSource(content.clone()),
+        format::json(),
+        &path,
+    ));
+
+    let configuration = builder.build().await.unwrap();
+    assert_eq!(Some(1), configuration.get("value"));
+    assert!(configuration.infos().all(|i| !i.is_stale()));
+
+    *content.lock().unwrap() = None;
+
+    let configuration = builder.build().await.unwrap();
+    assert_eq!(Some(1), configuration.get("value"));
+    let info = configuration.infos().next().unwrap();
+    assert!(info.is_stale());
+    assert!(info.cache_age().is_some());
+    assert!(info.to_string().contains("stale"));
+
+    *content.lock().unwrap() = Some(r#"{ "value" : 2 }"#.to_string());
+
+    let configuration = builder.build().await.unwrap();
+    assert_eq!(Some(2), configuration.get("value"));
+    assert_eq!(r#"{ "value" : 2 }"#, fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn test_cached_without_cache_returns_original_error() {
+    let path = cache_path("missing.json");
+    let content = Arc::new(Mutex::new(None));
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(Cached::new(
+        SwitchableSource(content),
+        format::json(),
+        &path,
+    ));
+
+    let error = builder.build().unwrap_err();
+
+    assert!(std::matches!(error.get_code(), ErrorCode::IoError(..)));
+    assert!(error.to_string().contains("No cached content available"));
+}
+
+#[test]
+fn test_cached_with_corrupt_cache_returns_cache_error() {
+    let path = cache_path("corrupt.json");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, r#"{ "value" : "#).unwrap();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(Cached::new(
+        SwitchableSource(Arc::new(Mutex::new(None))),
+        format::json(),
+        &path,
+    ));
+
+    let error = builder.build().unwrap_err();
+
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::DeserializationError(..)
+    ));
+    let error = error.to_string();
+    assert!(error.contains("Failed to deserialize cached content"));
+    assert!(error.contains("source unavailable"));
+    assert!(!error.contains("No cached content available"));
+}
+
+#[test]
+fn test_cached_with_unreadable_cache_returns_cache_error() {
+    // directory in place of cache file can not be read as one
+    let path = cache_path("unreadable.json");
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(Cached::new(
+        SwitchableSource(Arc::new(Mutex::new(None))),
+        format::json(),
+        &path,
+    ));
+
+    let error = builder.build().unwrap_err();
+    fs::remove_dir_all(&path).unwrap();
+
+    assert!(std::matches!(error.get_code(), ErrorCode::IoError(..)));
+    let error = error.to_string();
+    assert!(error.contains("Failed to read cached content"));
+    assert!(!error.contains("No cached content available"));
+}
+
+#[test]
+fn test_cached_does_not_store_malformed_content() {
+    let path = cache_path("malformed.json");
+    let content = Arc::new(Mutex::new(Some(r#"{ "value" : 1 }"#.to_string())));
+    let provider = Cached::new(SwitchableSource(content.clone()), format::json(), &path);
+
+    assert!(provider.cache_age().is_none());
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(provider);
+    builder.build().unwrap();
+
+    *content.lock().unwrap() = Some(r#"{ "value" : "#.to_string());
+    let error = builder.build().unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::DeserializationError(..)
+    ));
+
+    *content.lock().unwrap() = None;
+    let configuration = builder.build().unwrap();
+    assert_eq!(Some(1), configuration.get("value"));
+}
+
+// ----------- Test implementations --------------- //
+
+struct SwitchableSource(Arc<Mutex<Option<String>>>);
+
+impl Source for SwitchableSource {
+    fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        match *self.0.lock().unwrap() {
+            Some(ref content) => Ok(content.as_bytes().to_vec()),
+            None => Err(Error::new(ErrorKind::ConnectionRefused, "source unavailable").into()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        "switchable".into()
+    }
+}
+
+#[async_trait]
+impl AsyncSource for SwitchableSource {
+    async fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        Source::collect(self)
+    }
+
+    fn describe(&self) -> String {
+        Source::describe(self)
+    }
+}
@@ -0,0 +1,168 @@
+use miau::{
+    builder::ConfigurationBuilder, configuration::ConfigurationRead, error::ErrorCode, format,
+    provider::DirectoryProvider,
+};
+use std::path::PathBuf;
+
+fn conf_d() -> PathBuf {
+    // done like this for correct execution on different OS
+    ["tests", "files", "conf.d"].iter().collect()
+}
+
+#[test]
+fn test_directory_provider_layers_files_in_order() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(conf_d()));
+
+    let configuration = builder.build().unwrap();
+
+    assert!(configuration
+        .infos()
+        .map(|i| i.format())
+        .eq(vec!["json", "yaml"]));
+    assert_eq!(Some("20"), configuration.get("layer"));
+    assert_eq!(Some(8080), configuration.get("port"));
+    assert_eq!(Some("base"), configuration.get("name"));
+}
+
+#[test]
+fn test_directory_provider_infos_point_to_files() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(conf_d()));
+
+    let configuration = builder.build().unwrap();
+
+    let sources: Vec<&str> = configuration.infos().map(|i| i.source()).collect();
+    assert_eq!(2, sources.len());
+    assert!(sources[0].ends_with("10-base.json"));
+    assert!(sources[1].ends_with("20-override.yaml"));
+}
+
+#[test]
+fn test_directory_provider_with_pattern() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(conf_d()).pattern("*.json"));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(1, configuration.infos().count());
+    assert_eq!(Some("10"), configuration.get("layer"));
+}
+
+#[test]
+fn test_directory_provider_recursive() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(conf_d()).recursive(true));
+
+    let configuration = builder.build().unwrap();
+
+    assert!(configuration
+        .infos()
+        .map(|i| i.format())
+        .eq(vec!["json", "yaml", "toml"]));
+    assert_eq!(Some("30"), configuration.get("layer"));
+    assert_eq!(Some(true), configuration.get("nested"));
+}
+
+#[test]
+fn test_directory_provider_include_hidden() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(
+        DirectoryProvider::new(conf_d())
+            .pattern("*.json")
+            .recursive(true)
+            .include_hidden(true),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(3, configuration.infos().count());
+    assert_eq!(Some("base"), configuration.get("name"));
+}
+
+#[test]
+fn test_directory_provider_missing_directory() {
+    let path: PathBuf = ["tests", "files", "missing.d"].iter().collect();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(&path));
+    let error = builder.build().unwrap_err();
+    assert!(std::matches!(error.get_code(), ErrorCode::IoError(..)));
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(&path).allow_missing(true));
+    let configuration = builder.build().unwrap();
+    assert_eq!(0, configuration.infos().count());
+}
+
+#[test]
+fn test_directory_provider_empty_directory() {
+    let path: PathBuf = ["tests", "files", "empty.d"].iter().collect();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(&path));
+    let configuration = builder.build().unwrap();
+    assert_eq!(0, configuration.infos().count());
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(&path).allow_empty(false));
+    let error = builder.build().unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::EmptyConfiguration
+    ));
+}
+
+#[test]
+fn test_directory_provider_skips_unknown_format() {
+    let path: PathBuf = ["tests", "files", "unknown.d"].iter().collect();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(&path));
+    let configuration = builder.build().unwrap();
+    assert_eq!(0, configuration.infos().count());
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(&path).allow_empty(false));
+    let error = builder.build().unwrap_err();
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::EmptyConfiguration
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_directory_provider_symlink_cycle() {
+    use std::{env, fs, os::unix::fs::symlink};
+
+    let root = env::temp_dir().join(format!("miau-directory-cycle-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let nested = root.join("nested");
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(root.join("10-base.json"), r#"{ "layer" : 10 }"#).unwrap();
+    fs::write(nested.join("20-nested.json"), r#"{ "layer" : 20 }"#).unwrap();
+    symlink("..", nested.join("parent")).unwrap();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(&root).recursive(true));
+    let configuration = builder.build();
+
+    fs::remove_dir_all(&root).unwrap();
+
+    let configuration = configuration.unwrap();
+    assert_eq!(2, configuration.infos().count());
+    assert_eq!(Some(20), configuration.get("layer"));
+}
+
+#[test]
+fn test_directory_provider_with_custom_format() {
+    let path: PathBuf = ["tests", "files", "unknown.d"].iter().collect();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(DirectoryProvider::new(&path).with_format("txt", format::toml()));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some(1), configuration.get("value"));
+}
@@ -0,0 +1,95 @@
+use miau::{
+    builder::ConfigurationBuilder, configuration::ConfigurationRead, provider::DiscoveryProvider,
+};
+use std::{env, fs, path::Path};
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_discovery_provider() {
+    let root = env::temp_dir().join(format!("miau-discovery-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    write(
+        &root.join("etc").join("app").join("app.toml"),
+        "layer = \"system\"\nsystem = true",
+    );
+    write(
+        &root.join("xdg").join("app").join("app.toml"),
+        "layer = \"xdg\"",
+    );
+    write(
+        &root
+            .join("home")
+            .join(".config")
+            .join("app")
+            .join("app.toml"),
+        "layer = \"home\"",
+    );
+    write(
+        &root.join("project").join("app.toml"),
+        "layer = \"project\"",
+    );
+    write(
+        &root.join("project").join("sub").join("app.json"),
+        r#"{ "json" : true }"#,
+    );
+    let start = root.join("project").join("sub").join("deeper");
+    fs::create_dir_all(&start).unwrap();
+
+    env::set_var("XDG_CONFIG_HOME", root.join("xdg"));
+    env::set_var("HOME", root.join("home"));
+
+    let discovery = DiscoveryProvider::new("app", &["app.toml", "app.json"])
+        .system_dir(Some(root.join("etc")))
+        .start_dir(Some(&start));
+
+    let searched = discovery.searched_paths();
+    let found = discovery.found_paths();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(discovery);
+    let configuration = builder.build();
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert!(searched.contains(&start.join("app.toml")));
+    assert!(searched.contains(&root.join("etc").join("app").join("app.json")));
+    assert_eq!(
+        vec![
+            root.join("etc").join("app").join("app.toml"),
+            root.join("xdg").join("app").join("app.toml"),
+            root.join("home")
+                .join(".config")
+                .join("app")
+                .join("app.toml"),
+            root.join("project").join("app.toml"),
+            root.join("project").join("sub").join("app.json"),
+        ],
+        found
+    );
+
+    let configuration = configuration.unwrap();
+    assert_eq!(5, configuration.infos().count());
+    assert_eq!(Some("project"), configuration.get("layer"));
+    assert_eq!(Some(true), configuration.get("system"));
+    assert_eq!(Some(true), configuration.get("json"));
+}
+
+#[test]
+fn test_discovery_provider_nothing_found() {
+    let discovery = DiscoveryProvider::new("miau-surely-missing-application", &["missing.toml"])
+        .system_dir(None::<&Path>)
+        .start_dir(None::<&Path>);
+
+    assert!(discovery.found_paths().is_empty());
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(discovery);
+
+    let configuration = builder.build().unwrap();
+    assert_eq!(0, configuration.infos().count());
+}
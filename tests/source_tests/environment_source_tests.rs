@@ -75,3 +75,59 @@ fn test_environment_source_with_prefix() {
         ConfigurationRead::<'_, &str, &str>::get(&configuration, "notmy_t3_awesome_key")
     );
 }
+
+#[test]
+fn test_environment_source_with_separator() {
+    env::set_var("t4__db__host", "localhost");
+    env::set_var("t4__db__port", "5432");
+    env::set_var("t4__db____user", "skipped");
+    env::set_var("t4__name", "app");
+    env::set_var("t4__name__first", "conflicting");
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(EnvironmentProvider::with_prefix("t4__").separator("__"));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some("localhost"), configuration.get("t4:db:host"));
+    assert_eq!(Some(5432), configuration.get("t4:db:port"));
+    assert_eq!(2, configuration.lens().keys("t4:db").unwrap().len());
+
+    let name: Option<String> = configuration.get("t4:name");
+    assert_eq!(None, name);
+    assert_eq!(Some("conflicting"), configuration.get("t4:name:first"));
+}
+
+#[test]
+fn test_environment_source_prefers_nested_keys_on_conflicts() {
+    env::set_var("APP_A", "value");
+    env::set_var("APP_A_B", "nested");
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(
+        EnvironmentProvider::with_prefix("APP_")
+            .strip_prefix(true)
+            .separator("_"),
+    );
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some("nested"), configuration.get("A:B"));
+    assert_eq!(1, configuration.lens().keys("A").unwrap().len());
+}
+
+#[test]
+fn test_environment_source_stripping_prefix() {
+    env::set_var("t5_key", "value");
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(EnvironmentProvider::with_prefix("t5_").strip_prefix(true));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some("value"), configuration.get("key"));
+    assert_eq!(
+        None,
+        ConfigurationRead::<'_, &str, &str>::get(&configuration, "t5_key")
+    );
+}
@@ -0,0 +1,97 @@
+use miau::{
+    builder::ConfigurationBuilder, configuration::ConfigurationRead, error::ErrorCode,
+    provider::KeyPerFileProvider,
+};
+use std::{env, path::PathBuf};
+
+fn secrets() -> PathBuf {
+    // done like this for correct execution on different OS
+    ["tests", "files", "secrets"].iter().collect()
+}
+
+#[test]
+fn test_key_per_file_provider() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(KeyPerFileProvider::new(secrets()));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(1, configuration.infos().count());
+    assert_eq!(Some("hunter2"), configuration.get("db_password"));
+    assert_eq!(Some("admin"), configuration.get("database:user"));
+    assert_eq!(Some(5432), configuration.get("database:port"));
+}
+
+#[test]
+fn test_key_per_file_provider_without_trimming() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(KeyPerFileProvider::new(secrets()).trim(false));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some("hunter2\n"), configuration.get("db_password"));
+    assert_eq!(Some("  admin  \n"), configuration.get("database:user"));
+}
+
+#[test]
+fn test_key_per_file_provider_missing_directory() {
+    let path: PathBuf = ["tests", "files", "missing_secrets"].iter().collect();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(KeyPerFileProvider::new(&path));
+    let error = builder.build().unwrap_err();
+    assert!(std::matches!(error.get_code(), ErrorCode::IoError(..)));
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(KeyPerFileProvider::new(&path).allow_missing(true));
+    let configuration = builder.build().unwrap();
+    assert_eq!(0, configuration.infos().count());
+}
+
+#[test]
+fn test_key_per_file_provider_systemd_credentials() {
+    env::set_var("CREDENTIALS_DIRECTORY", secrets());
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(KeyPerFileProvider::credentials());
+
+    env::remove_var("CREDENTIALS_DIRECTORY");
+
+    let configuration = builder.build().unwrap();
+    assert_eq!(Some("hunter2"), configuration.get("db_password"));
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(KeyPerFileProvider::credentials());
+
+    let configuration = builder.build().unwrap();
+    assert_eq!(0, configuration.infos().count());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_key_per_file_provider_kubernetes_layout() {
+    use std::{fs, os::unix::fs::symlink};
+
+    // mimics layout of Kubernetes volume-mounted ConfigMap
+    let root = env::temp_dir().join(format!("miau-key-per-file-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let timestamped = root.join("..2021_01_01_00_00_00.000000000");
+    fs::create_dir_all(&timestamped).unwrap();
+    fs::write(timestamped.join("log_level"), "debug\n").unwrap();
+    symlink("..2021_01_01_00_00_00.000000000", root.join("..data")).unwrap();
+    symlink("..data/log_level", root.join("log_level")).unwrap();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(KeyPerFileProvider::new(&root));
+    let configuration = builder.build();
+
+    fs::remove_dir_all(&root).unwrap();
+
+    let configuration = configuration.unwrap();
+    let map = configuration
+        .try_convert_into::<std::collections::HashMap<String, String>>()
+        .unwrap();
+
+    assert_eq!(1, map.len());
+    assert_eq!(Some("debug"), map.get("log_level").map(|v| v.as_str()));
+}
@@ -1,3 +1,9 @@
+mod cached_tests;
 mod config_provider_tests;
+mod directory_provider_tests;
+mod discovery_provider_tests;
 mod environment_source_tests;
 mod file_source_tests;
+mod key_per_file_provider_tests;
+mod optional_tests;
+mod resilience_tests;
@@ -0,0 +1,174 @@
+use async_trait::async_trait;
+use miau::{
+    builder::{AsyncConfigurationBuilder, ConfigurationBuilder},
+    configuration::{Configuration, ConfigurationInfo, ConfigurationRead},
+    error::{ConfigurationError, ErrorCode},
+    format,
+    provider::{AsyncProvider, Optional, Provider},
+    source::{AsyncSource, FileSource, InMemorySource},
+};
+use std::{
+    io::{Error, ErrorKind},
+    path::PathBuf,
+};
+
+#[test]
+fn test_optional_missing_file_is_skipped() {
+    let path: PathBuf = ["tests", "files", "optional", "missing.json"]
+        .iter()
+        .collect();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add(
+        InMemorySource::from_string_slice(r#"{ "value" : 1 }"#),
+        format::json(),
+    );
+    builder.add_provider(Optional::synchronous(
+        FileSource::from_path(path),
+        format::json(),
+    ));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some(1), configuration.get("value"));
+    assert!(configuration
+        .infos()
+        .map(|i| i.is_skipped())
+        .eq(vec![false, true]));
+    assert_eq!(
+        Some("source not found"),
+        configuration.infos().nth(1).unwrap().skip_reason()
+    );
+    assert_eq!(1, configuration.try_convert_into::<Config>().unwrap().value);
+}
+
+#[test]
+fn test_optional_empty_file_is_skipped() {
+    let path: PathBuf = ["tests", "files", "optional", "blank.json"]
+        .iter()
+        .collect();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(Optional::synchronous(
+        FileSource::from_path(path),
+        format::json(),
+    ));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(
+        Some("source is empty"),
+        configuration.infos().next().unwrap().skip_reason()
+    );
+}
+
+#[test]
+fn test_optional_malformed_file_is_not_skipped() {
+    let path: PathBuf = ["tests", "files", "optional", "malformed.json"]
+        .iter()
+        .collect();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(Optional::synchronous(
+        FileSource::from_path(path),
+        format::json(),
+    ));
+
+    let error = builder.build().unwrap_err();
+
+    assert!(std::matches!(
+        error.get_code(),
+        ErrorCode::DeserializationError(..)
+    ));
+}
+
+#[test]
+fn test_optional_existing_file_is_not_skipped() {
+    let path: PathBuf = ["tests", "files", "config1.json"].iter().collect();
+
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(Optional::synchronous(
+        FileSource::from_path(path),
+        format::json(),
+    ));
+
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(Some(1), configuration.get("value1"));
+    assert!(configuration.infos().all(|i| !i.is_skipped()));
+}
+
+struct FailingProvider(ErrorKind);
+
+impl Provider for FailingProvider {
+    fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        Err(Error::new(self.0, "failing provider").into())
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        ConfigurationInfo::new("failing", "failing")
+    }
+}
+
+#[async_trait]
+impl AsyncProvider for FailingProvider {
+    async fn collect(&self) -> Result<Configuration, ConfigurationError> {
+        Provider::collect(self)
+    }
+
+    fn describe(&self) -> ConfigurationInfo {
+        Provider::describe(self)
+    }
+}
+
+#[test]
+fn test_optional_provider_permission_error_is_propagated() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(Optional::new(FailingProvider(ErrorKind::PermissionDenied)));
+
+    let error = builder.build().unwrap_err();
+
+    assert!(std::matches!(error.get_code(), ErrorCode::IoError(..)));
+}
+
+#[test]
+fn test_optional_provider_not_found_is_skipped() {
+    let mut builder = ConfigurationBuilder::default();
+    builder.add_provider(Optional::new(FailingProvider(ErrorKind::NotFound)));
+
+    let configuration = builder.build().unwrap();
+
+    let info = configuration.infos().next().unwrap();
+    assert_eq!("failing", info.source());
+    assert!(info.is_skipped());
+    assert!(info.to_string().contains("skipped"));
+}
+
+struct EmptyAsyncSource;
+
+#[async_trait]
+impl AsyncSource for EmptyAsyncSource {
+    async fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        Ok(Vec::new())
+    }
+
+    fn describe(&self) -> String {
+        "empty".into()
+    }
+}
+
+#[tokio::test]
+async fn test_optional_async() {
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_provider_async(Optional::asynchronous(EmptyAsyncSource, format::json()));
+    builder.add_provider_async(Optional::new(FailingProvider(ErrorKind::NotFound)));
+
+    let configuration = builder.build().await.unwrap();
+
+    assert_eq!(2, configuration.infos().filter(|i| i.is_skipped()).count());
+}
+
+#[derive(serde::Deserialize)]
+struct Config {
+    value: i32,
+}
@@ -0,0 +1,262 @@
+use async_trait::async_trait;
+use miau::{
+    builder::AsyncConfigurationBuilder,
+    configuration::ConfigurationRead,
+    error::{ConfigurationError, ErrorCode},
+    format,
+    provider::{Retry, RetryPolicy, Timeout, Timer},
+    source::AsyncSource,
+};
+use rstest::rstest;
+use std::{
+    io::{Error, ErrorKind},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+#[tokio::test]
+async fn test_retry_succeeds_after_failures() {
+    let timer = RecordingTimer::default();
+    let source = FlakySource::new(2, ErrorKind::ConnectionRefused);
+    let attempts = source.attempts.clone();
+
+    let policy = RetryPolicy::new(3)
+        .backoff(Duration::from_millis(100), Duration::from_millis(150))
+        .jitter(0.0);
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(Retry::new(source, policy, timer.clone()), format::json());
+
+    let configuration = builder.build().await.unwrap();
+
+    assert_eq!(Some(1), configuration.get("value"));
+    assert_eq!(3, attempts.load(Ordering::SeqCst));
+    assert_eq!(
+        vec![Duration::from_millis(100), Duration::from_millis(150)],
+        *timer.sleeps.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_after_max_retries() {
+    let timer = RecordingTimer::default();
+    let source = FlakySource::new(10, ErrorKind::ConnectionRefused);
+    let attempts = source.attempts.clone();
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(
+        Retry::new(source, RetryPolicy::new(2), timer.clone()),
+        format::json(),
+    );
+
+    let error = builder.build().await.unwrap_err();
+
+    assert!(std::matches!(error.get_code(), ErrorCode::IoError(..)));
+    assert_eq!(3, attempts.load(Ordering::SeqCst));
+    assert_eq!(2, timer.sleeps.lock().unwrap().len());
+}
+
+#[tokio::test]
+async fn test_retry_jitter_shortens_delays() {
+    let timer = RecordingTimer::default();
+    let source = FlakySource::new(3, ErrorKind::ConnectionRefused);
+
+    let policy = RetryPolicy::new(3)
+        .backoff(Duration::from_millis(100), Duration::from_secs(1))
+        .multiplier(3.0)
+        .jitter(0.5);
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(Retry::new(source, policy, timer.clone()), format::json());
+    builder.build().await.unwrap();
+
+    let sleeps = timer.sleeps.lock().unwrap();
+    for (sleep, max) in sleeps.iter().zip(&[100, 300, 900]) {
+        assert!(*sleep <= Duration::from_millis(*max));
+        assert!(*sleep >= Duration::from_millis(*max / 2));
+    }
+}
+
+#[rstest(
+    multiplier,
+    jitter,
+    case(-1.0, 0.0),
+    case(0.5, 0.0),
+    case(f64::NAN, 0.0),
+    case(f64::INFINITY, 0.0),
+    case(2.0, f64::NAN)
+)]
+fn test_retry_invalid_policy_values_do_not_panic(multiplier: f64, jitter: f64) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let timer = RecordingTimer::default();
+    let source = FlakySource::new(3, ErrorKind::ConnectionRefused);
+
+    let policy = RetryPolicy::new(3)
+        .backoff(Duration::from_millis(100), Duration::from_secs(1))
+        .multiplier(multiplier)
+        .jitter(jitter);
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(Retry::new(source, policy, timer.clone()), format::json());
+    runtime.block_on(builder.build()).unwrap();
+
+    let sleeps = timer.sleeps.lock().unwrap();
+    assert_eq!(3, sleeps.len());
+    for sleep in sleeps.iter() {
+        assert!(*sleep <= Duration::from_secs(1));
+    }
+}
+
+#[tokio::test]
+async fn test_retry_does_not_retry_non_retryable_errors() {
+    let timer = RecordingTimer::default();
+    let source = FlakySource::new(1, ErrorKind::NotFound);
+    let attempts = source.attempts.clone();
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(
+        Retry::new(source, RetryPolicy::default(), timer.clone()),
+        format::json(),
+    );
+
+    builder.build().await.unwrap_err();
+
+    assert_eq!(1, attempts.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_retry_custom_classification() {
+    let timer = RecordingTimer::default();
+    let source = FlakySource::new(1, ErrorKind::NotFound);
+    let attempts = source.attempts.clone();
+
+    let policy =
+        RetryPolicy::new(1).retry_if(|e| std::matches!(e.get_code(), ErrorCode::IoError(..)));
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(Retry::new(source, policy, timer.clone()), format::json());
+
+    builder.build().await.unwrap();
+
+    assert_eq!(2, attempts.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_timeout_fails_slow_source() {
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(
+        Timeout::new(
+            SlowSource(Duration::from_secs(5)),
+            Duration::from_millis(10),
+            tokio::time::sleep,
+        ),
+        format::json(),
+    );
+
+    let error = builder.build().await.unwrap_err();
+
+    match error.get_code() {
+        ErrorCode::IoError(e) => assert_eq!(ErrorKind::TimedOut, e.kind()),
+        _ => panic!("expected I/O error"),
+    }
+}
+
+#[tokio::test]
+async fn test_timeout_passes_fast_source() {
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(
+        Timeout::new(
+            SlowSource(Duration::from_millis(1)),
+            Duration::from_secs(5),
+            tokio::time::sleep,
+        ),
+        format::json(),
+    );
+
+    let configuration = builder.build().await.unwrap();
+
+    assert_eq!(Some(1), configuration.get("value"));
+}
+
+#[tokio::test]
+async fn test_retry_of_timed_out_attempts() {
+    let source = Timeout::new(
+        SlowSource(Duration::from_secs(5)),
+        Duration::from_millis(10),
+        tokio::time::sleep,
+    );
+    let policy = RetryPolicy::new(2).backoff(Duration::from_millis(1), Duration::from_millis(1));
+
+    let mut builder = AsyncConfigurationBuilder::default();
+    builder.add_async(
+        Retry::new(source, policy, tokio::time::sleep),
+        format::json(),
+    );
+
+    let error = builder.build().await.unwrap_err();
+
+    assert!(error.to_string().contains("Giving up after 3 attempt(s)"));
+}
+
+// ----------- Test implementations --------------- //
+
+#[derive(Default, Clone)]
+struct RecordingTimer {
+    sleeps: Arc<Mutex<Vec<Duration>>>,
+}
+
+#[async_trait]
+impl Timer for RecordingTimer {
+    async fn sleep(&self, duration: Duration) {
+        self.sleeps.lock().unwrap().push(duration);
+    }
+}
+
+struct FlakySource {
+    failures: usize,
+    kind: ErrorKind,
+    attempts: Arc<AtomicUsize>,
+}
+
+impl FlakySource {
+    fn new(failures: usize, kind: ErrorKind) -> Self {
+        FlakySource {
+            failures,
+            kind,
+            attempts: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncSource for FlakySource {
+    async fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        let attempt = self.attempts.fetch_add(1, Ordering::SeqCst);
+        if attempt < self.failures {
+            Err(Error::new(self.kind, "flaky source").into())
+        } else {
+            Ok(br#"{ "value" : 1 }"#.to_vec())
+        }
+    }
+
+    fn describe(&self) -> String {
+        "flaky".into()
+    }
+}
+
+struct SlowSource(Duration);
+
+#[async_trait]
+impl AsyncSource for SlowSource {
+    async fn collect(&self) -> Result<Vec<u8>, ConfigurationError> {
+        tokio::time::sleep(self.0).await;
+        Ok(br#"{ "value" : 1 }"#.to_vec())
+    }
+
+    fn describe(&self) -> String {
+        "slow".into()
+    }
+}
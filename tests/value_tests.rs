@@ -1,5 +1,5 @@
 use miau::{
-    configuration::Value,
+    configuration::{Strict, Value},
     error::{ConfigurationError, ErrorCode},
 };
 use std::convert::TryInto;
@@ -35,7 +35,7 @@ fn test_float_variant_conversion() {
     // a little tweak
     let value = Value::Float(1f64);
     let boolean: bool = (&value).try_into().unwrap();
-    assert_eq!(true, boolean);
+    assert!(boolean);
 }
 
 #[test]
@@ -74,7 +74,7 @@ fn test_string_variant_conversion() {
     // little tweak
     let strv = Value::String("true".into());
     let boolean: bool = (&strv).try_into().unwrap();
-    assert_eq!(true, boolean);
+    assert!(boolean);
 }
 
 #[test]
@@ -99,7 +99,7 @@ fn test_integer_variant_conversion() {
     ));
 
     let boolean: bool = (&value).try_into().unwrap();
-    assert_eq!(true, boolean);
+    assert!(boolean);
 }
 
 #[test]
@@ -120,5 +120,61 @@ fn test_boolean_variant_conversion() {
     assert_eq!("true", string_ref);
 
     let boolean: bool = (&value).try_into().unwrap();
-    assert_eq!(true, boolean);
+    assert!(boolean);
+}
+
+fn assert_wrong_value_type<T>(result: Result<Strict<T>, ConfigurationError>) {
+    match result {
+        Ok(_) => panic!("expected strict conversion to fail"),
+        Err(error) => assert!(std::matches!(
+            error.get_code(),
+            ErrorCode::WrongValueType(..)
+        )),
+    }
+}
+
+#[test]
+fn test_strict_conversion_accepts_lossless_same_type_values() {
+    let int: Strict<i8> = (&Value::SignedInteger(-7)).try_into().unwrap();
+    assert_eq!(-7, int.into_inner());
+
+    let flt: Strict<f32> = (&Value::Float(0.5)).try_into().unwrap();
+    assert_eq!(0.5, flt.into_inner());
+
+    let flt: Strict<f32> = (&Value::Float(0.1)).try_into().unwrap();
+    assert_eq!(0.1f32, flt.into_inner());
+
+    let flt: Strict<f32> = (&Value::Float(-f64::from(f32::MAX))).try_into().unwrap();
+    assert_eq!(f32::MIN, flt.into_inner());
+
+    let boolean: Strict<bool> = (&Value::Bool(false)).try_into().unwrap();
+    assert!(!boolean.into_inner());
+
+    let value = Value::String("word".into());
+    let string_owned: Strict<String> = (&value).try_into().unwrap();
+    assert_eq!("word", string_owned.into_inner());
+    let string_ref: Strict<&str> = (&value).try_into().unwrap();
+    assert_eq!("word", string_ref.into_inner());
+}
+
+#[test]
+fn test_strict_conversion_rejects_coercions() {
+    assert_wrong_value_type::<bool>((&Value::String("1".into())).try_into());
+    assert_wrong_value_type::<bool>((&Value::SignedInteger(1)).try_into());
+    assert_wrong_value_type::<i32>((&Value::Float(3.9)).try_into());
+    assert_wrong_value_type::<i32>((&Value::Float(3.0)).try_into());
+    assert_wrong_value_type::<i32>((&Value::Bool(true)).try_into());
+    assert_wrong_value_type::<i32>((&Value::String("12".into())).try_into());
+    assert_wrong_value_type::<f64>((&Value::Bool(true)).try_into());
+    assert_wrong_value_type::<f64>((&Value::SignedInteger(1)).try_into());
+    assert_wrong_value_type::<f32>((&Value::SignedInteger(1)).try_into());
+    assert_wrong_value_type::<String>((&Value::SignedInteger(12)).try_into());
+    assert_wrong_value_type::<&str>((&Value::Bool(true)).try_into());
+}
+
+#[test]
+fn test_strict_conversion_rejects_lossy_numbers() {
+    assert_wrong_value_type::<i8>((&Value::SignedInteger(300)).try_into());
+    assert_wrong_value_type::<f32>((&Value::Float(1e300)).try_into());
+    assert_wrong_value_type::<f32>((&Value::Float(-1e39)).try_into());
 }